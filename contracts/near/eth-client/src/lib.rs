@@ -216,6 +216,18 @@ impl EthClient {
         }
     }
 
+    /// Same as `block_hash_safe`, but for each of `block_numbers`
+    #[result_serializer(borsh)]
+    pub fn block_hashes_safe(
+        &self,
+        #[serializer(borsh)] block_numbers: Vec<u64>,
+    ) -> Vec<Option<H256>> {
+        block_numbers
+            .into_iter()
+            .map(|index| self.block_hash_safe(index))
+            .collect()
+    }
+
     /// Add the block header to the client.
     /// `block_header` -- RLP-encoded Ethereum header;
     /// `dag_nodes` -- dag nodes with their merkle proofs.
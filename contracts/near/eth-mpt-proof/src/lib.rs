@@ -0,0 +1,372 @@
+//! Verification of the Ethereum Merkle-Patricia trie proofs, e.g. `eth_getProof` results or
+//! the proofs of the receipts, transactions and withdrawals of a block.
+//!
+//! The verifier never panics on the malformed input, so it can be used both on-chain and by the
+//! off-chain tools checking the proofs before submitting them.
+//!
+//! Patricia Trie: https://eth.wiki/en/fundamentals/patricia-tree
+//! Verification:  https://github.com/slockit/in3/wiki/Ethereum-Verification-and-MerkleProof#receipt-proof
+
+use eth_types::{near_keccak256, H256};
+use rlp::Rlp;
+use std::fmt;
+
+/// The number of items in the branch node: 16 children and the value
+const BRANCH_NODE_ITEM_COUNT: usize = 17;
+
+/// The number of items in the leaf and extension nodes: the encoded path and the value or child
+const PATH_NODE_ITEM_COUNT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofError {
+    /// The proof doesn't contain any nodes
+    EmptyProof,
+    /// The node doesn't match the hash or the embedded node referenced by its parent
+    InvalidNodeHash,
+    /// The node is not a valid RLP-encoded branch, extension or leaf node
+    InvalidNode,
+    /// The proof contains nodes after the value or the absence of the key is proven
+    UnexpectedNode,
+    /// The proof ends before the value or the absence of the key is proven
+    IncompleteProof,
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::EmptyProof => "The proof is empty",
+            Self::InvalidNodeHash => "The node hash doesn't match its reference",
+            Self::InvalidNode => "The node is not a valid trie node",
+            Self::UnexpectedNode => "The proof contains unexpected nodes",
+            Self::IncompleteProof => "The proof is incomplete",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for ProofError {}
+
+impl From<rlp::DecoderError> for ProofError {
+    fn from(_: rlp::DecoderError) -> Self {
+        Self::InvalidNode
+    }
+}
+
+/// Verifies the proof of `key` in the trie with the root `root`.
+/// `key` is the unhashed trie key, e.g. `rlp(index)` for the receipts trie or
+/// `keccak256(address)` for the state trie.
+///
+/// Returns `Ok(Some(value))` if the key is included in the trie, `Ok(None)` if the proof shows
+/// that the key is absent from the trie, or the error if the proof is invalid.
+pub fn verify_proof(
+    root: H256,
+    key: &[u8],
+    proof: &[Vec<u8>],
+) -> Result<Option<Vec<u8>>, ProofError> {
+    if proof.is_empty() {
+        return Err(ProofError::EmptyProof);
+    }
+
+    let key: Vec<u8> = key
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect();
+    let mut key_index = 0;
+    let mut expected_node = NodeReference::Hash(root.0.as_bytes().to_vec());
+
+    for (proof_index, node) in proof.iter().enumerate() {
+        if !expected_node.matches(node) {
+            return Err(ProofError::InvalidNodeHash);
+        }
+
+        let is_last_node = proof_index + 1 == proof.len();
+        let node = Rlp::new(node);
+        let next_node = match node.item_count()? {
+            BRANCH_NODE_ITEM_COUNT => {
+                if key_index == key.len() {
+                    Step::Done(non_empty(node.at(16)?.data()?))
+                } else {
+                    let child = NodeReference::from_rlp(&node.at(key[key_index] as usize)?)?;
+                    key_index += 1;
+                    match child {
+                        // The key would continue in the empty child, so it is absent
+                        Some(child) => Step::Next(child),
+                        None => Step::Done(None),
+                    }
+                }
+            }
+            PATH_NODE_ITEM_COUNT => {
+                let (path, is_leaf) = decode_path(node.at(0)?.data()?)?;
+                let path_matches = key[key_index..].starts_with(&path);
+                key_index += path.len();
+
+                if is_leaf {
+                    if path_matches && key_index == key.len() {
+                        Step::Done(non_empty(node.at(1)?.data()?))
+                    } else {
+                        // The leaf of another key is placed where the key would be
+                        Step::Done(None)
+                    }
+                } else if path_matches {
+                    match NodeReference::from_rlp(&node.at(1)?)? {
+                        Some(child) => Step::Next(child),
+                        None => return Err(ProofError::InvalidNode),
+                    }
+                } else {
+                    // The key diverges from the extension path, so it is absent
+                    Step::Done(None)
+                }
+            }
+            _ => return Err(ProofError::InvalidNode),
+        };
+
+        match next_node {
+            Step::Done(_) if !is_last_node => return Err(ProofError::UnexpectedNode),
+            Step::Done(value) => return Ok(value),
+            Step::Next(child) => expected_node = child,
+        }
+    }
+
+    Err(ProofError::IncompleteProof)
+}
+
+/// Returns `true` if the proof shows that `key` is absent from the trie with the root `root`.
+pub fn verify_exclusion_proof(root: H256, key: &[u8], proof: &[Vec<u8>]) -> bool {
+    matches!(verify_proof(root, key, proof), Ok(None))
+}
+
+/// The result of processing a single node of the proof
+enum Step {
+    /// The value of the key is found or its absence is proven
+    Done(Option<Vec<u8>>),
+    /// The key continues in the child node
+    Next(NodeReference),
+}
+
+/// The reference to the node from its parent
+enum NodeReference {
+    /// Keccak256 of the RLP-encoded node
+    Hash(Vec<u8>),
+    /// The RLP-encoded node shorter than 32 bytes is embedded into its parent
+    Embedded(Vec<u8>),
+}
+
+impl NodeReference {
+    /// Decodes the child reference, returns `None` for the empty child
+    fn from_rlp(item: &Rlp) -> Result<Option<Self>, ProofError> {
+        if item.is_list() {
+            return Ok(Some(Self::Embedded(item.as_raw().to_vec())));
+        }
+
+        let data = item.data()?;
+        match data.len() {
+            0 => Ok(None),
+            32 => Ok(Some(Self::Hash(data.to_vec()))),
+            _ => Err(ProofError::InvalidNode),
+        }
+    }
+
+    fn matches(&self, node: &[u8]) -> bool {
+        match self {
+            Self::Hash(hash) => near_keccak256(node).as_slice() == hash.as_slice(),
+            Self::Embedded(embedded_node) => node == embedded_node.as_slice(),
+        }
+    }
+}
+
+/// Decodes the hex-prefix encoded path of the leaf or extension node into nibbles.
+/// Returns the nibbles and whether the node is a leaf.
+fn decode_path(encoded_path: &[u8]) -> Result<(Vec<u8>, bool), ProofError> {
+    let (first_byte, rest) = encoded_path.split_first().ok_or(ProofError::InvalidNode)?;
+    let flag = first_byte >> 4;
+    if flag > 3 {
+        return Err(ProofError::InvalidNode);
+    }
+
+    let mut path = vec![];
+    if flag % 2 == 1 {
+        // The path has an odd length, the first nibble is packed with the flag
+        path.push(first_byte & 0x0f);
+    } else if first_byte & 0x0f != 0 {
+        return Err(ProofError::InvalidNode);
+    }
+    path.extend(rest.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+
+    Ok((path, flag >= 2))
+}
+
+/// The value of the branch or leaf node, the empty value means there is no value
+fn non_empty(value: &[u8]) -> Option<Vec<u8>> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rlp::RlpStream;
+
+    fn hash(node: &[u8]) -> H256 {
+        H256(near_keccak256(node).into())
+    }
+
+    fn encode_path(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flag = if is_leaf { 2 } else { 0 };
+        let mut path = if nibbles.len() % 2 == 1 {
+            vec![((flag + 1) << 4) | nibbles[0]]
+        } else {
+            vec![flag << 4]
+        };
+        let rest = &nibbles[nibbles.len() % 2..];
+        path.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+        path
+    }
+
+    fn path_node(nibbles: &[u8], is_leaf: bool, item: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&encode_path(nibbles, is_leaf));
+        stream.append(&item);
+        stream.out().to_vec()
+    }
+
+    /// The branch node with the hashed children
+    fn branch_node(children: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(17);
+        for index in 0..16 {
+            match children
+                .iter()
+                .find(|(child_index, _)| *child_index == index)
+            {
+                Some((_, child)) => stream.append(&hash(child).0.as_bytes()),
+                None => stream.append_empty_data(),
+            };
+        }
+        stream.append_empty_data();
+        stream.out().to_vec()
+    }
+
+    fn nibbles(key: &[u8]) -> Vec<u8> {
+        key.iter()
+            .flat_map(|byte| [byte >> 4, byte & 0x0f])
+            .collect()
+    }
+
+    const VALUE: &[u8] = b"the value stored in the trie node, long enough to be hashed";
+
+    #[test]
+    fn test_single_leaf_trie() {
+        let key = [0x12, 0x34];
+        let leaf = path_node(&nibbles(&key), true, VALUE);
+        let root = hash(&leaf);
+        let proof = vec![leaf];
+
+        assert_eq!(verify_proof(root, &key, &proof), Ok(Some(VALUE.to_vec())));
+        // The leaf of another key proves the absence of the key
+        assert_eq!(verify_proof(root, &[0x12, 0x35], &proof), Ok(None));
+        assert!(verify_exclusion_proof(root, &[0x12, 0x35], &proof));
+        assert!(!verify_exclusion_proof(root, &key, &proof));
+    }
+
+    #[test]
+    fn test_branch_trie() {
+        let key_a = [0x12, 0x34];
+        let key_b = [0x52, 0x34];
+        let leaf_a = path_node(&nibbles(&key_a)[1..], true, VALUE);
+        let leaf_b = path_node(
+            &nibbles(&key_b)[1..],
+            true,
+            b"another value of the trie node",
+        );
+        let branch = branch_node(&[(1, &leaf_a), (5, &leaf_b)]);
+        let root = hash(&branch);
+
+        assert_eq!(
+            verify_proof(root, &key_a, &[branch.clone(), leaf_a.clone()]),
+            Ok(Some(VALUE.to_vec()))
+        );
+        // The proof of the other key doesn't prove the value of this key
+        assert_eq!(
+            verify_proof(root, &key_a, &[branch.clone(), leaf_b]),
+            Err(ProofError::InvalidNodeHash)
+        );
+        // The branch child for the first nibble is empty
+        let proof = vec![branch];
+        assert!(verify_exclusion_proof(root, &[0x22, 0x34], &proof));
+        assert_eq!(
+            verify_proof(root, &key_a, &proof),
+            Err(ProofError::IncompleteProof)
+        );
+        assert_eq!(
+            verify_proof(root, &[0x22, 0x34], &[proof[0].clone(), leaf_a]),
+            Err(ProofError::UnexpectedNode)
+        );
+    }
+
+    #[test]
+    fn test_extension_with_embedded_leaves() {
+        let key_a = [0xab, 0xc1];
+        let key_b = [0xab, 0xc2];
+        // The short leaves are embedded into the branch node
+        let leaf_a = path_node(&[], true, b"a");
+        let leaf_b = path_node(&[], true, b"b");
+        let mut stream = RlpStream::new_list(17);
+        for index in 0..16 {
+            match index {
+                1 => stream.append_raw(&leaf_a, 1),
+                2 => stream.append_raw(&leaf_b, 1),
+                _ => stream.append_empty_data(),
+            };
+        }
+        stream.append_empty_data();
+        let branch = stream.out().to_vec();
+        let extension = path_node(&[0xa, 0xb, 0xc], false, hash(&branch).0.as_bytes());
+        let root = hash(&extension);
+
+        let proof = vec![extension.clone(), branch, leaf_b];
+        assert_eq!(verify_proof(root, &key_b, &proof), Ok(Some(b"b".to_vec())));
+        assert_eq!(
+            verify_proof(root, &key_a, &proof),
+            Err(ProofError::InvalidNodeHash)
+        );
+        // The key diverges from the extension path
+        assert!(verify_exclusion_proof(root, &[0xad, 0xc1], &[extension]));
+    }
+
+    #[test]
+    fn test_invalid_proof() {
+        let key = [0x12, 0x34];
+        let leaf = path_node(&nibbles(&key), true, VALUE);
+        let root = hash(&leaf);
+
+        let proof = vec![leaf.clone(), leaf];
+
+        assert_eq!(verify_proof(root, &key, &[]), Err(ProofError::EmptyProof));
+        assert_eq!(
+            verify_proof(H256::default(), &key, &proof[..1]),
+            Err(ProofError::InvalidNodeHash)
+        );
+        assert_eq!(
+            verify_proof(root, &key, &proof),
+            Err(ProofError::UnexpectedNode)
+        );
+
+        let not_a_node = rlp::encode_list::<Vec<u8>, _>(&[vec![0x20], vec![], vec![]]).to_vec();
+        assert_eq!(
+            verify_proof(hash(&not_a_node), &key, &[not_a_node]),
+            Err(ProofError::InvalidNode)
+        );
+        let invalid_rlp = vec![0xf8, 0xff];
+        assert_eq!(
+            verify_proof(hash(&invalid_rlp), &key, &[invalid_rlp]),
+            Err(ProofError::InvalidNode)
+        );
+        assert!(!verify_exclusion_proof(
+            H256::default(),
+            &[0x22],
+            &[vec![0xc0]]
+        ));
+    }
+}
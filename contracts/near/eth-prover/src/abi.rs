@@ -0,0 +1,279 @@
+use eth_types::*;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+
+/// The size of the ABI-encoded word
+const WORD_SIZE: usize = 32;
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy, PartialEq)]
+pub enum AbiError {
+    /// The event signature is malformed or uses the types which are not supported
+    InvalidSignature,
+    /// The first topic of the log entry is not the hash of the event signature
+    SignatureMismatch,
+    /// The topics or data of the log entry don't match the event parameters
+    InvalidData,
+}
+
+/// The elementary ABI types. Arrays and tuples are not supported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AbiType {
+    Address,
+    Bool,
+    /// The number of bits
+    Uint(usize),
+    /// The number of bits
+    Int(usize),
+    /// The number of bytes
+    FixedBytes(usize),
+    Bytes,
+    String,
+}
+
+impl AbiType {
+    fn parse(name: &str) -> Result<Self, AbiError> {
+        let parse_size = |size: &str, default: usize| -> Result<usize, AbiError> {
+            if size.is_empty() {
+                Ok(default)
+            } else {
+                size.parse().map_err(|_| AbiError::InvalidSignature)
+            }
+        };
+
+        let abi_type = match name {
+            "address" => Self::Address,
+            "bool" => Self::Bool,
+            "bytes" => Self::Bytes,
+            "string" => Self::String,
+            _ => {
+                if let Some(bits) = name.strip_prefix("uint") {
+                    Self::Uint(parse_size(bits, 256)?)
+                } else if let Some(bits) = name.strip_prefix("int") {
+                    Self::Int(parse_size(bits, 256)?)
+                } else if let Some(bytes) = name.strip_prefix("bytes") {
+                    Self::FixedBytes(parse_size(bytes, 0)?)
+                } else {
+                    return Err(AbiError::InvalidSignature);
+                }
+            }
+        };
+
+        match abi_type {
+            Self::Uint(bits) | Self::Int(bits) if bits == 0 || bits > 256 || bits % 8 != 0 => {
+                Err(AbiError::InvalidSignature)
+            }
+            Self::FixedBytes(bytes) if bytes == 0 || bytes > WORD_SIZE => {
+                Err(AbiError::InvalidSignature)
+            }
+            _ => Ok(abi_type),
+        }
+    }
+
+    fn canonical_name(&self) -> String {
+        match self {
+            Self::Address => "address".to_string(),
+            Self::Bool => "bool".to_string(),
+            Self::Uint(bits) => format!("uint{}", bits),
+            Self::Int(bits) => format!("int{}", bits),
+            Self::FixedBytes(bytes) => format!("bytes{}", bytes),
+            Self::Bytes => "bytes".to_string(),
+            Self::String => "string".to_string(),
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        matches!(self, Self::Bytes | Self::String)
+    }
+
+    /// Decodes the value of the static type from the ABI-encoded word
+    fn decode_word(&self, word: &[u8]) -> Result<AbiValue, AbiError> {
+        let is_zero = |bytes: &[u8]| bytes.iter().all(|byte| *byte == 0);
+        match *self {
+            Self::Address if is_zero(&word[..12]) => {
+                let address: [u8; 20] = word[12..].try_into().unwrap();
+                Ok(AbiValue::Address(H160(address.into())))
+            }
+            Self::Bool if is_zero(&word[..31]) && word[31] <= 1 => {
+                Ok(AbiValue::Bool(word[31] == 1))
+            }
+            Self::Uint(bits) if is_zero(&word[..WORD_SIZE - bits / 8]) => {
+                Ok(AbiValue::Uint(U256(word.into())))
+            }
+            Self::Int(bits) => {
+                // The value is sign-extended to the whole word
+                let padding = WORD_SIZE - bits / 8;
+                let sign_byte = if word[padding] & 0x80 == 0 {
+                    0x00
+                } else {
+                    0xff
+                };
+                if word[..padding].iter().all(|byte| *byte == sign_byte) {
+                    Ok(AbiValue::Int(U256(word.into())))
+                } else {
+                    Err(AbiError::InvalidData)
+                }
+            }
+            Self::FixedBytes(bytes) if is_zero(&word[bytes..]) => {
+                Ok(AbiValue::FixedBytes(word[..bytes].to_vec()))
+            }
+            _ => Err(AbiError::InvalidData),
+        }
+    }
+}
+
+/// The decoded event parameter
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub enum AbiValue {
+    Address(Address),
+    Bool(bool),
+    Uint(U256),
+    /// The two's complement representation of the value
+    Int(U256),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    /// Keccak256 of the indexed `bytes` or `string` parameter, the value itself is not logged
+    Hash(H256),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventParam {
+    pub abi_type: AbiType,
+    pub indexed: bool,
+}
+
+/// The event declaration, e.g. `Transfer(address indexed from, address indexed to, uint256 value)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventSignature {
+    pub name: String,
+    pub params: Vec<EventParam>,
+}
+
+impl EventSignature {
+    /// Parses the event declaration. The parameter names are optional and ignored.
+    pub fn parse(signature: &str) -> Result<Self, AbiError> {
+        let signature = signature.trim();
+        let (name, params) = signature
+            .strip_suffix(')')
+            .and_then(|signature| signature.split_once('('))
+            .ok_or(AbiError::InvalidSignature)?;
+        let name = name.trim();
+        if name.is_empty() || params.contains(['(', ')', '[', ']']) {
+            return Err(AbiError::InvalidSignature);
+        }
+
+        let params = if params.trim().is_empty() {
+            vec![]
+        } else {
+            params
+                .split(',')
+                .map(|param| {
+                    let mut words = param.split_whitespace();
+                    let abi_type = AbiType::parse(words.next().ok_or(AbiError::InvalidSignature)?)?;
+                    let mut words = words.peekable();
+                    let indexed = words.next_if_eq(&"indexed").is_some();
+                    // The optional parameter name
+                    words.next();
+                    if words.next().is_some() {
+                        return Err(AbiError::InvalidSignature);
+                    }
+                    Ok(EventParam { abi_type, indexed })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            params,
+        })
+    }
+
+    /// Returns the canonical signature, e.g. `Transfer(address,address,uint256)`
+    pub fn canonical(&self) -> String {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|param| param.abi_type.canonical_name())
+            .collect();
+        format!("{}({})", self.name, params.join(","))
+    }
+
+    /// Returns the first topic of the logs emitted by the event
+    pub fn topic(&self) -> H256 {
+        H256(near_keccak256(self.canonical().as_bytes()).into())
+    }
+
+    /// Decodes the parameters of the event from the log entry in the order of declaration
+    pub fn decode_log(&self, topics: &[H256], data: &[u8]) -> Result<Vec<AbiValue>, AbiError> {
+        if topics.first() != Some(&self.topic()) {
+            return Err(AbiError::SignatureMismatch);
+        }
+
+        let indexed_count = self.params.iter().filter(|param| param.indexed).count();
+        if topics.len() != indexed_count + 1 {
+            return Err(AbiError::InvalidData);
+        }
+
+        let mut topics = topics[1..].iter();
+        let mut head_offset = 0;
+        self.params
+            .iter()
+            .map(|param| {
+                if param.indexed {
+                    let topic = topics.next().unwrap();
+                    if param.abi_type.is_dynamic() {
+                        Ok(AbiValue::Hash(*topic))
+                    } else {
+                        param.abi_type.decode_word(topic.0.as_bytes())
+                    }
+                } else {
+                    let word = read_word(data, head_offset)?;
+                    head_offset += WORD_SIZE;
+                    if param.abi_type.is_dynamic() {
+                        decode_dynamic(param.abi_type, data, word)
+                    } else {
+                        param.abi_type.decode_word(word)
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn read_word(data: &[u8], offset: usize) -> Result<&[u8], AbiError> {
+    data.get(offset..offset.checked_add(WORD_SIZE).ok_or(AbiError::InvalidData)?)
+        .ok_or(AbiError::InvalidData)
+}
+
+/// Converts the word to `usize` used for the offsets and lengths
+fn word_to_usize(word: &[u8]) -> Result<usize, AbiError> {
+    if word[..WORD_SIZE - 8].iter().any(|byte| *byte != 0) {
+        return Err(AbiError::InvalidData);
+    }
+    usize::try_from(u64::from_be_bytes(
+        word[WORD_SIZE - 8..].try_into().unwrap(),
+    ))
+    .map_err(|_| AbiError::InvalidData)
+}
+
+/// Decodes `bytes` or `string` from the tail of `data`, `offset_word` is the word in the head
+fn decode_dynamic(
+    abi_type: AbiType,
+    data: &[u8],
+    offset_word: &[u8],
+) -> Result<AbiValue, AbiError> {
+    let offset = word_to_usize(offset_word)?;
+    let length = word_to_usize(read_word(data, offset)?)?;
+    let start = offset + WORD_SIZE;
+    let value = start
+        .checked_add(length)
+        .and_then(|end| data.get(start..end))
+        .ok_or(AbiError::InvalidData)?
+        .to_vec();
+
+    match abi_type {
+        AbiType::String => String::from_utf8(value)
+            .map(AbiValue::String)
+            .map_err(|_| AbiError::InvalidData),
+        _ => Ok(AbiValue::Bytes(value)),
+    }
+}
@@ -0,0 +1,35 @@
+use eth_types::events::NearEvent;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::Serialize;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum ProofKind {
+    LogEntry,
+    StorageProof,
+    Transaction,
+    Account,
+    Withdrawal,
+}
+
+/// Events emitted by the prover in the NEP-297 format:
+/// `EVENT_JSON:{"standard":"eth-prover","version":"1.0.0","event":"...","data":{...}}`
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(
+    crate = "near_sdk::serde",
+    tag = "event",
+    content = "data",
+    rename_all = "snake_case"
+)]
+pub enum EthProverEvent {
+    /// The proof is verified against the block known to the EthClient
+    ProofVerified {
+        proof_kind: ProofKind,
+        block_number: u64,
+    },
+}
+
+impl NearEvent for EthProverEvent {
+    const STANDARD: &'static str = "eth-prover";
+    const VERSION: &'static str = "1.0.0";
+}
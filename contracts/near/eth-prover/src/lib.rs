@@ -4,20 +4,63 @@ use near_plugins::{
     Upgradable,
 };
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
-    env, ext_contract, near_bindgen, Gas, PanicOnDefault, Promise, PromiseOrValue, PublicKey,
+    env, ext_contract, is_promise_success, near_bindgen, require, Balance, Gas, GasWeight,
+    PanicOnDefault, Promise, PromiseOrValue, PublicKey,
 };
 use rlp::Rlp;
+use std::collections::BTreeMap;
+
+use crate::abi::{AbiError, AbiValue, EventSignature};
+use crate::events::{EthProverEvent, ProofKind};
+use crate::transaction::{decode_transaction, Transaction};
+use eth_types::events::NearEvent;
+
+pub mod abi;
+mod events;
+mod migrate;
+pub mod transaction;
 
 type AccountId = String;
 
 /// Gas to call block_hash_safe
 const BLOCK_HASH_SAFE_GAS: Gas = Gas(10_000_000_000_000);
 
+/// Gas to call recent_block_hash_safe and historical_block_hash_safe
+const BLOCK_HASH_PROOF_GAS: Gas = Gas(20_000_000_000_000);
+
 /// Gas to call on_block_hash
 const ON_BLOCK_HASH_GAS: Gas = Gas(5_000_000_000_000);
 
+/// Gas to call last_block_number
+const LAST_BLOCK_NUMBER_GAS: Gas = Gas(5_000_000_000_000);
+
+/// Gas to call block_hashes_safe for each distinct block
+const BLOCK_HASHES_SAFE_GAS_PER_BLOCK: Gas = Gas(2_000_000_000_000);
+
+/// Gas to call on_block_hashes for each distinct block
+const ON_BLOCK_HASHES_GAS_PER_BLOCK: Gas = Gas(1_000_000_000_000);
+
+/// Gas to call header_roots_safe
+const HEADER_ROOTS_SAFE_GAS: Gas = Gas(10_000_000_000_000);
+
+/// Gas to call on_header_roots_for_log_entry and on_header_roots_for_storage_proof
+const ON_HEADER_ROOTS_GAS: Gas = Gas(50_000_000_000_000);
+
+/// Gas to call deposit_reward_pool
+const DEPOSIT_REWARD_POOL_GAS: Gas = Gas(5_000_000_000_000);
+
+/// The maximum number of log entries verified by a single `verify_log_entries` call
+const MAX_LOG_ENTRIES_PER_BATCH: usize = 32;
+
+/// The maximum number of storage slots verified by a single `verify_storage_proofs` call
+const MAX_STORAGE_SLOTS_PER_BATCH: usize = 32;
+
+/// Gas to call on_fees_withdrawn
+const ON_FEES_WITHDRAWN_GAS: Gas = Gas(5_000_000_000_000);
+
 #[derive(AccessControlRole, Deserialize, Serialize, Copy, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum Role {
@@ -27,6 +70,9 @@ pub enum Role {
     UpgradableCodeStager,
     UpgradableCodeDeployer,
     DAO,
+    UnrestrictedVerifyTransaction,
+    UnrestrictedVerifyWithdrawal,
+    UnrestrictedVerifyAccount,
 }
 
 #[near_bindgen]
@@ -44,6 +90,96 @@ pub struct EthProver {
     bridge_smart_contract: AccountId,
     #[deprecated]
     paused: u128,
+    /// The fees which should be attached to the verification methods
+    fee_schedule: FeeSchedule,
+    /// The collected fees which are not withdrawn yet
+    accumulated_fees: Balance,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeSchedule {
+    /// Charged by the `verify_log_entry*` methods
+    pub log_entry_fee: U128,
+    /// Charged by the `verify_storage_proof*` methods
+    pub storage_proof_fee: U128,
+    /// Charged by the `verify_transaction` method
+    pub transaction_fee: U128,
+    /// Charged by the `verify_account` method
+    pub account_proof_fee: U128,
+    /// Charged by the `verify_withdrawal` method
+    pub withdrawal_fee: U128,
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self {
+            log_entry_fee: U128(0),
+            storage_proof_fee: U128(0),
+            transaction_fee: U128(0),
+            account_proof_fee: U128(0),
+            withdrawal_fee: U128(0),
+        }
+    }
+}
+
+/// The inclusion proof of a single log entry for `verify_log_entries`
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LogEntryProof {
+    pub log_index: u64,
+    pub log_entry_data: Vec<u8>,
+    pub receipt_index: u64,
+    pub receipt_data: Vec<u8>,
+    pub header_data: Vec<u8>,
+    pub proof: Vec<Vec<u8>>,
+}
+
+/// The proof of a single storage slot for `verify_storage_proofs`
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct StorageSlotProof {
+    /// Keccak256 of the storage key
+    pub storage_key_hash: Vec<u8>,
+    pub storage_proof: Vec<Vec<u8>>,
+    pub expected_storage_value: Vec<u8>,
+}
+
+/// The log entry proven by `verify_log_entry_with_result`
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub struct VerifiedLogEntry {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+    pub block_number: u64,
+    pub block_hash: H256,
+    /// The status of the receipt which contains the log entry
+    pub receipt_status: bool,
+    /// The event parameters decoded with the given event signature
+    pub decoded_params: Option<Vec<AbiValue>>,
+}
+
+/// The reason why `verify_log_entry_with_result` failed
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy, PartialEq)]
+pub enum LogEntryVerificationError {
+    /// The receipt is not included into the receipts trie of the header
+    InvalidReceiptProof,
+    /// The receipt doesn't contain the log entry at `log_index`
+    LogEntryMismatch,
+    /// The block is not finalized by the EthClient yet
+    BlockNotFinalized,
+    /// The hash of the finalized block is already removed by the EthClient gc,
+    /// or isn't stored by the EthClient running in the sparse mode
+    BlockGarbageCollected,
+    /// The header is not the one of the finalized block with the same number
+    BlockHashMismatch,
+    /// The log entry can't be decoded with the given event signature
+    Abi(AbiError),
+}
+
+/// The result of `verify_log_entry_with_result`
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub enum LogEntryVerificationResult {
+    Verified(VerifiedLogEntry),
+    Failed(LogEntryVerificationError),
 }
 
 /// Defines an interface to call EthProver back as a callback with the result from the
@@ -52,6 +188,61 @@ pub struct EthProver {
 pub trait RemoteSelf {
     #[result_serializer(borsh)]
     fn on_block_hash(&self, #[serializer(borsh)] expected_block_hash: H256) -> bool;
+
+    #[result_serializer(borsh)]
+    fn on_proof_block_hash(
+        &self,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] proof_kind: ProofKind,
+    ) -> bool;
+
+    #[result_serializer(borsh)]
+    fn on_log_entry_block_hash(
+        &self,
+        #[serializer(borsh)] verified_log_entry: VerifiedLogEntry,
+    ) -> LogEntryVerificationResult;
+
+    #[result_serializer(borsh)]
+    fn on_block_hashes(
+        &self,
+        #[serializer(borsh)] block_numbers: Vec<u64>,
+        #[serializer(borsh)] expected_block_hashes: Vec<H256>,
+    ) -> bool;
+
+    #[result_serializer(borsh)]
+    fn on_header_roots_for_log_entry(
+        &self,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] log_index: u64,
+        #[serializer(borsh)] log_entry_data: Vec<u8>,
+        #[serializer(borsh)] receipt_index: u64,
+        #[serializer(borsh)] receipt_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+    ) -> bool;
+
+    #[result_serializer(borsh)]
+    fn on_header_roots_for_storage_proof(
+        &self,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] account_proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] contract_address: Vec<u8>,
+        #[serializer(borsh)] expected_account_state: Vec<u8>,
+        #[serializer(borsh)] storage_key_hash: Vec<u8>,
+        #[serializer(borsh)] storage_proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] expected_storage_value: Vec<u8>,
+    ) -> bool;
+
+    fn on_proof_result_block_hash(
+        &self,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] proof_kind: ProofKind,
+        #[serializer(borsh)] result: Vec<u8>,
+        #[serializer(borsh)] failure_result: Vec<u8>,
+    );
+
+    fn on_fees_withdrawn(&mut self, amount: U128);
 }
 
 /// Defines an interface to call EthClient contract to get the safe block hash for a given block
@@ -61,12 +252,42 @@ pub trait RemoteSelf {
 pub trait RemoteEthClient {
     #[result_serializer(borsh)]
     fn block_hash_safe(&self, #[serializer(borsh)] index: u64) -> Option<H256>;
+
+    #[result_serializer(borsh)]
+    fn block_hashes_safe(&self, #[serializer(borsh)] block_numbers: Vec<u64>) -> Vec<Option<H256>>;
+
+    #[result_serializer(borsh)]
+    fn last_block_number(&self) -> u64;
+
+    #[result_serializer(borsh)]
+    fn header_roots_safe(&self, #[serializer(borsh)] block_number: u64)
+        -> Option<BlockHeaderRoots>;
+
+    fn deposit_reward_pool(&mut self);
+}
+
+/// Proof of the header inclusion into the canonical chain which is verified by the EthClient
+/// instead of the lookup of the stored block hashes.
+enum BlockHashProof {
+    /// Borsh-serialized `BlockRootsProof`
+    BlockRoots(Vec<u8>),
+    /// Borsh-serialized `HistoricalBlockProof`
+    Historical(Vec<u8>),
 }
 
-/// Get element at position `pos` from rlp encoded data,
-/// and decode it as vector of bytes
-fn get_vec(data: &Rlp, pos: usize) -> Vec<u8> {
-    data.at(pos).unwrap().as_val::<Vec<u8>>().unwrap()
+impl BlockHashProof {
+    fn method_name(&self) -> &'static str {
+        match self {
+            BlockHashProof::BlockRoots(_) => "recent_block_hash_safe",
+            BlockHashProof::Historical(_) => "historical_block_hash_safe",
+        }
+    }
+
+    fn into_args(self) -> Vec<u8> {
+        match self {
+            BlockHashProof::BlockRoots(args) | BlockHashProof::Historical(args) => args,
+        }
+    }
 }
 
 #[near_bindgen]
@@ -78,6 +299,8 @@ impl EthProver {
         let mut contract = Self {
             bridge_smart_contract,
             paused: 0,
+            fee_schedule: FeeSchedule::default(),
+            accumulated_fees: 0,
         };
 
         contract.acl_init_super_admin(near_sdk::env::predecessor_account_id());
@@ -100,6 +323,212 @@ impl EthProver {
         return block_hash == Some(expected_block_hash);
     }
 
+    /// Implementation of the callback when the EthClient returns the block hash of the block
+    /// the proof is verified against. Emits the `proof_verified` event if the hash matches.
+    /// This method can only be called by the EthProver contract itself (e.g. as callback).
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_proof_block_hash(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        block_hash: Option<H256>,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] proof_kind: ProofKind,
+    ) -> bool {
+        if block_hash != Some(expected_block_hash) {
+            return false;
+        }
+
+        EthProverEvent::ProofVerified {
+            proof_kind,
+            block_number,
+        }
+        .emit();
+        true
+    }
+
+    /// Implementation of the callback when the EthClient returns the block hash and the last
+    /// finalized block number for `verify_log_entry_with_result`. Emits the `proof_verified`
+    /// event if the hash matches.
+    /// This method can only be called by the EthProver contract itself (e.g. as callback).
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_log_entry_block_hash(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        block_hash: Option<H256>,
+        #[callback]
+        #[serializer(borsh)]
+        last_block_number: u64,
+        #[serializer(borsh)] verified_log_entry: VerifiedLogEntry,
+    ) -> LogEntryVerificationResult {
+        let err = match block_hash {
+            Some(block_hash) if block_hash == verified_log_entry.block_hash => {
+                EthProverEvent::ProofVerified {
+                    proof_kind: ProofKind::LogEntry,
+                    block_number: verified_log_entry.block_number,
+                }
+                .emit();
+                return LogEntryVerificationResult::Verified(verified_log_entry);
+            }
+            Some(_) => LogEntryVerificationError::BlockHashMismatch,
+            None if verified_log_entry.block_number > last_block_number => {
+                LogEntryVerificationError::BlockNotFinalized
+            }
+            None => LogEntryVerificationError::BlockGarbageCollected,
+        };
+        LogEntryVerificationResult::Failed(err)
+    }
+
+    /// Implementation of the callback when the EthClient returns the hashes of the blocks
+    /// for `verify_log_entries`. Emits the `proof_verified` event for each block if all
+    /// the hashes match.
+    /// This method can only be called by the EthProver contract itself (e.g. as callback).
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_block_hashes(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        block_hashes: Vec<Option<H256>>,
+        #[serializer(borsh)] block_numbers: Vec<u64>,
+        #[serializer(borsh)] expected_block_hashes: Vec<H256>,
+    ) -> bool {
+        if block_hashes.len() != expected_block_hashes.len()
+            || block_hashes
+                .iter()
+                .zip(expected_block_hashes)
+                .any(|(block_hash, expected_block_hash)| *block_hash != Some(expected_block_hash))
+        {
+            return false;
+        }
+
+        for block_number in block_numbers {
+            EthProverEvent::ProofVerified {
+                proof_kind: ProofKind::LogEntry,
+                block_number,
+            }
+            .emit();
+        }
+        true
+    }
+
+    /// Implementation of the callback when the EthClient returns the hash of the block
+    /// the proof of `proof_kind` is verified against. Returns `result`, the borsh-serialized
+    /// verification result, if the hash matches, otherwise `failure_result`.
+    /// This method can only be called by the EthProver contract itself (e.g. as callback).
+    #[private]
+    pub fn on_proof_result_block_hash(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        block_hash: Option<H256>,
+        #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] proof_kind: ProofKind,
+        #[serializer(borsh)] result: Vec<u8>,
+        #[serializer(borsh)] failure_result: Vec<u8>,
+    ) {
+        if self.on_proof_block_hash(block_hash, expected_block_hash, block_number, proof_kind) {
+            env::value_return(&result);
+        } else {
+            env::value_return(&failure_result);
+        }
+    }
+
+    /// Implementation of the callback when the EthClient returns the header roots for
+    /// `verify_log_entry_by_block_number`.
+    /// This method can only be called by the EthProver contract itself (e.g. as callback).
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_header_roots_for_log_entry(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        header_roots: Option<BlockHeaderRoots>,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] log_index: u64,
+        #[serializer(borsh)] log_entry_data: Vec<u8>,
+        #[serializer(borsh)] receipt_index: u64,
+        #[serializer(borsh)] receipt_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+    ) -> bool {
+        let header_roots = match header_roots {
+            Some(header_roots) => header_roots,
+            None => {
+                env::log_str("The header roots are not available in the EthClient");
+                return false;
+            }
+        };
+
+        if !Self::verify_log_entry_inclusion(
+            header_roots.receipts_root,
+            log_index,
+            &log_entry_data,
+            receipt_index,
+            &receipt_data,
+            proof,
+        ) {
+            return false;
+        }
+
+        EthProverEvent::ProofVerified {
+            proof_kind: ProofKind::LogEntry,
+            block_number,
+        }
+        .emit();
+        true
+    }
+
+    /// Implementation of the callback when the EthClient returns the header roots for
+    /// `verify_storage_proof_by_block_number`.
+    /// This method can only be called by the EthProver contract itself (e.g. as callback).
+    #[result_serializer(borsh)]
+    #[private]
+    pub fn on_header_roots_for_storage_proof(
+        &self,
+        #[callback]
+        #[serializer(borsh)]
+        header_roots: Option<BlockHeaderRoots>,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] account_proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] contract_address: Vec<u8>,
+        #[serializer(borsh)] expected_account_state: Vec<u8>,
+        #[serializer(borsh)] storage_key_hash: Vec<u8>,
+        #[serializer(borsh)] storage_proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] expected_storage_value: Vec<u8>,
+    ) -> bool {
+        let header_roots = match header_roots {
+            Some(header_roots) => header_roots,
+            None => {
+                env::log_str("The header roots are not available in the EthClient");
+                return false;
+            }
+        };
+
+        if !Self::verify_storage_proof_inclusion(
+            header_roots.state_root,
+            account_proof,
+            &contract_address,
+            &expected_account_state,
+            storage_key_hash,
+            storage_proof,
+            &expected_storage_value,
+        ) {
+            return false;
+        }
+
+        EthProverEvent::ProofVerified {
+            proof_kind: ProofKind::StorageProof,
+            block_number,
+        }
+        .emit();
+        true
+    }
+
     /// Externally visible method to verify that the given block hash is part of the safe canonical
     /// chain on the remote EthClient contract.
     /// Returns a promise.
@@ -132,6 +561,7 @@ impl EthProver {
         #[serializer(borsh)] proof: Vec<Vec<u8>>,
         #[serializer(borsh)] skip_bridge_call: bool,
     ) -> PromiseOrValue<bool> {
+        self.check_fee_paid(self.fee_schedule.log_entry_fee.0, skip_bridge_call);
         let min_header_height = None;
         let max_header_height = None;
         self.verify_log_entry_internal(
@@ -144,6 +574,122 @@ impl EthProver {
             min_header_height,
             max_header_height,
             skip_bridge_call,
+            None,
+        )
+    }
+
+    /// Same as `verify_log_entry`, but returns the proven log entry with the block and receipt
+    /// details, or the reason why the verification failed.
+    /// If `event_signature` is provided, e.g. `Transfer(address indexed from, address indexed to,
+    /// uint256 value)`, the topics and data of the log entry are decoded as the event parameters.
+    #[pause(except(roles(Role::UnrestrictedVerifyLogEntry, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_log_entry_with_result(
+        &self,
+        #[serializer(borsh)] log_index: u64,
+        #[serializer(borsh)] log_entry_data: Vec<u8>,
+        #[serializer(borsh)] receipt_index: u64,
+        #[serializer(borsh)] receipt_data: Vec<u8>,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] event_signature: Option<String>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<LogEntryVerificationResult> {
+        self.check_fee_paid(self.fee_schedule.log_entry_fee.0, skip_bridge_call);
+        let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
+        let verified_log_entry = match Self::verify_log_entry_details(
+            &header,
+            log_index,
+            &log_entry_data,
+            receipt_index,
+            &receipt_data,
+            proof,
+            event_signature.as_deref(),
+        ) {
+            Ok(verified_log_entry) => verified_log_entry,
+            Err(err) => return PromiseOrValue::Value(LogEntryVerificationResult::Failed(err)),
+        };
+
+        if skip_bridge_call {
+            return PromiseOrValue::Value(LogEntryVerificationResult::Verified(verified_log_entry));
+        }
+
+        // The last block number tells the not yet finalized blocks from the removed ones
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(BLOCK_HASH_SAFE_GAS)
+            .block_hash_safe(header.number)
+            .and(
+                eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+                    .with_static_gas(LAST_BLOCK_NUMBER_GAS)
+                    .last_block_number(),
+            )
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_BLOCK_HASH_GAS)
+                    .on_log_entry_block_hash(verified_log_entry),
+            )
+            .into()
+    }
+
+    /// Same as `verify_log_entry`, but the header is verified by the EthClient with
+    /// `historical_block_proof` (a borsh-serialized `HistoricalBlockProof`) instead of the
+    /// stored block hashes. Allows to verify log entries from the blocks that are already
+    /// garbage collected by the EthClient.
+    #[pause(except(roles(Role::UnrestrictedVerifyLogEntry, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_log_entry_with_historical_proof(
+        &self,
+        #[serializer(borsh)] log_index: u64,
+        #[serializer(borsh)] log_entry_data: Vec<u8>,
+        #[serializer(borsh)] receipt_index: u64,
+        #[serializer(borsh)] receipt_data: Vec<u8>,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] historical_block_proof: Vec<u8>,
+    ) -> PromiseOrValue<bool> {
+        self.check_fee_paid(self.fee_schedule.log_entry_fee.0, false);
+        self.verify_log_entry_internal(
+            log_index,
+            log_entry_data,
+            receipt_index,
+            receipt_data,
+            header_data,
+            proof,
+            None,
+            None,
+            false,
+            Some(BlockHashProof::Historical(historical_block_proof)),
+        )
+    }
+
+    /// Same as `verify_log_entry`, but the header is verified by the EthClient with
+    /// `block_roots_proof` (a borsh-serialized `BlockRootsProof`) instead of the stored block
+    /// hashes. Allows to verify log entries from the recent blocks which are not stored by
+    /// the EthClient running in the sparse mode.
+    #[pause(except(roles(Role::UnrestrictedVerifyLogEntry, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_log_entry_with_block_roots_proof(
+        &self,
+        #[serializer(borsh)] log_index: u64,
+        #[serializer(borsh)] log_entry_data: Vec<u8>,
+        #[serializer(borsh)] receipt_index: u64,
+        #[serializer(borsh)] receipt_data: Vec<u8>,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] block_roots_proof: Vec<u8>,
+    ) -> PromiseOrValue<bool> {
+        self.check_fee_paid(self.fee_schedule.log_entry_fee.0, false);
+        self.verify_log_entry_internal(
+            log_index,
+            log_entry_data,
+            receipt_index,
+            receipt_data,
+            header_data,
+            proof,
+            None,
+            None,
+            false,
+            Some(BlockHashProof::BlockRoots(block_roots_proof)),
         )
     }
 
@@ -161,6 +707,7 @@ impl EthProver {
         #[serializer(borsh)] max_header_height: Option<u64>,
         #[serializer(borsh)] skip_bridge_call: bool,
     ) -> PromiseOrValue<bool> {
+        self.check_fee_paid(self.fee_schedule.log_entry_fee.0, skip_bridge_call);
         self.verify_log_entry_internal(
             log_index,
             log_entry_data,
@@ -171,40 +718,134 @@ impl EthProver {
             min_header_height,
             max_header_height,
             skip_bridge_call,
+            None,
         )
     }
 
-    fn verify_log_entry_internal(
+    /// Same as `verify_log_entry`, but instead of the header the receipts root is fetched
+    /// from the EthClient by `block_number`. Requires the EthClient to store the header roots.
+    #[pause(except(roles(Role::UnrestrictedVerifyLogEntry, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_log_entry_by_block_number(
         &self,
         #[serializer(borsh)] log_index: u64,
         #[serializer(borsh)] log_entry_data: Vec<u8>,
         #[serializer(borsh)] receipt_index: u64,
         #[serializer(borsh)] receipt_data: Vec<u8>,
-        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] block_number: u64,
         #[serializer(borsh)] proof: Vec<Vec<u8>>,
-        #[serializer(borsh)] min_header_height: Option<u64>,
-        #[serializer(borsh)] max_header_height: Option<u64>,
-        #[serializer(borsh)] skip_bridge_call: bool,
     ) -> PromiseOrValue<bool> {
-        let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
-        if !Self::is_block_height_in_bound(header.number, min_header_height, max_header_height) {
-            return PromiseOrValue::Value(false);
-        }
-
-        let log_entry: LogEntry = rlp::decode(log_entry_data.as_slice()).unwrap();
-        let receipt: Receipt = rlp::decode(receipt_data.as_slice()).unwrap();
+        self.check_fee_paid(self.fee_schedule.log_entry_fee.0, false);
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(HEADER_ROOTS_SAFE_GAS)
+            .header_roots_safe(block_number)
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_HEADER_ROOTS_GAS)
+                    .on_header_roots_for_log_entry(
+                        block_number,
+                        log_index,
+                        log_entry_data,
+                        receipt_index,
+                        receipt_data,
+                        proof,
+                    ),
+            )
+            .into()
+    }
 
-        // Verify log_entry included in receipt
-        let log_index_usize = usize::try_from(log_index).expect("Invalid log_index");
-        assert_eq!(receipt.logs[log_index_usize], log_entry);
+    /// Verifies all the log entries locally and checks the distinct headers against
+    /// the EthClient with a single `block_hashes_safe` call.
+    /// Returns `true` only if every log entry is proven.
+    #[pause(except(roles(Role::UnrestrictedVerifyLogEntry, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_log_entries(
+        &self,
+        #[serializer(borsh)] log_entry_proofs: Vec<LogEntryProof>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<bool> {
+        require!(!log_entry_proofs.is_empty(), "No log entries to verify");
+        require!(
+            log_entry_proofs.len() <= MAX_LOG_ENTRIES_PER_BATCH,
+            format!(
+                "The list of log entries is too long, max {} log entries are allowed",
+                MAX_LOG_ENTRIES_PER_BATCH
+            )
+        );
+        self.check_fee_paid(
+            self.fee_schedule.log_entry_fee.0 * log_entry_proofs.len() as u128,
+            skip_bridge_call,
+        );
 
-        // Verify receipt included into header
-        let data = Self::verify_trie_proof(
+        let mut block_hashes = BTreeMap::new();
+        for log_entry_proof in log_entry_proofs {
+            let header: BlockHeader = rlp::decode(log_entry_proof.header_data.as_slice()).unwrap();
+            if !Self::verify_log_entry_inclusion(
+                header.receipts_root,
+                log_entry_proof.log_index,
+                &log_entry_proof.log_entry_data,
+                log_entry_proof.receipt_index,
+                &log_entry_proof.receipt_data,
+                log_entry_proof.proof,
+            ) {
+                return PromiseOrValue::Value(false);
+            }
+
+            let block_hash = header.hash.unwrap();
+            if *block_hashes.entry(header.number).or_insert(block_hash) != block_hash {
+                env::log_str(
+                    format!("Different headers are provided for block {}", header.number).as_str(),
+                );
+                return PromiseOrValue::Value(false);
+            }
+        }
+
+        if skip_bridge_call {
+            return PromiseOrValue::Value(true);
+        }
+
+        let blocks_count = block_hashes.len() as u64;
+        let (block_numbers, expected_block_hashes): (Vec<u64>, Vec<H256>) =
+            block_hashes.into_iter().unzip();
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(BLOCK_HASH_SAFE_GAS + BLOCK_HASHES_SAFE_GAS_PER_BLOCK * blocks_count)
+            .block_hashes_safe(block_numbers.clone())
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(
+                        ON_BLOCK_HASH_GAS + ON_BLOCK_HASHES_GAS_PER_BLOCK * blocks_count,
+                    )
+                    .on_block_hashes(block_numbers, expected_block_hashes),
+            )
+            .into()
+    }
+
+    fn verify_log_entry_internal(
+        &self,
+        #[serializer(borsh)] log_index: u64,
+        #[serializer(borsh)] log_entry_data: Vec<u8>,
+        #[serializer(borsh)] receipt_index: u64,
+        #[serializer(borsh)] receipt_data: Vec<u8>,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] min_header_height: Option<u64>,
+        #[serializer(borsh)] max_header_height: Option<u64>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+        #[serializer(borsh)] block_hash_proof: Option<BlockHashProof>,
+    ) -> PromiseOrValue<bool> {
+        let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
+        if !Self::is_block_height_in_bound(header.number, min_header_height, max_header_height) {
+            return PromiseOrValue::Value(false);
+        }
+
+        let verification_result = Self::verify_log_entry_inclusion(
             header.receipts_root,
-            rlp::encode(&receipt_index).to_vec(),
+            log_index,
+            &log_entry_data,
+            receipt_index,
+            &receipt_data,
             proof,
         );
-        let verification_result = receipt_data == data;
         if verification_result && skip_bridge_call {
             return PromiseOrValue::Value(true);
         } else if !verification_result {
@@ -212,14 +853,113 @@ impl EthProver {
         }
 
         // Verify block header was in the bridge
-        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
-            .with_static_gas(BLOCK_HASH_SAFE_GAS)
-            .block_hash_safe(header.number)
-            .then(
-                remote_self::ext(env::current_account_id())
-                    .with_static_gas(ON_BLOCK_HASH_GAS)
-                    .on_block_hash(header.hash.unwrap()),
-            )
+        self.verify_block_hash(&header, block_hash_proof, ProofKind::LogEntry)
+    }
+
+    /// Verifies that the transaction `tx_data` (the EIP-2718 envelope or the RLP-encoded legacy
+    /// transaction) is included into the transactions trie of the header at `tx_index`.
+    /// Returns the decoded transaction with the sender recovered from its signature,
+    /// or `None` if the proof is invalid.
+    #[pause(except(roles(Role::UnrestrictedVerifyTransaction, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_transaction(
+        &self,
+        #[serializer(borsh)] tx_index: u64,
+        #[serializer(borsh)] tx_data: Vec<u8>,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Option<Transaction>> {
+        self.check_fee_paid(self.fee_schedule.transaction_fee.0, skip_bridge_call);
+        let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
+        let transaction = match Self::verify_transaction_inclusion(
+            header.transactions_root,
+            tx_index,
+            &tx_data,
+            proof,
+        ) {
+            Some(transaction) => transaction,
+            None => return PromiseOrValue::Value(None),
+        };
+
+        if skip_bridge_call {
+            return PromiseOrValue::Value(Some(transaction));
+        }
+
+        // Verify block header was in the bridge
+        self.verify_proof_result_block_hash(&header, ProofKind::Transaction, transaction)
+            .into()
+    }
+
+    /// Verifies that the RLP-encoded withdrawal is included into the withdrawals trie of
+    /// the header at `withdrawal_position` (the position in the block withdrawals list).
+    /// Returns the decoded withdrawal, or `None` if the proof is invalid.
+    #[pause(except(roles(Role::UnrestrictedVerifyWithdrawal, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_withdrawal(
+        &self,
+        #[serializer(borsh)] withdrawal_position: u64,
+        #[serializer(borsh)] withdrawal_data: Vec<u8>,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Option<Withdrawal>> {
+        self.check_fee_paid(self.fee_schedule.withdrawal_fee.0, skip_bridge_call);
+        let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
+        let withdrawals_root = match header.withdrawals_root {
+            Some(withdrawals_root) => withdrawals_root,
+            None => {
+                env::log_str("The header doesn't contain the withdrawals root");
+                return PromiseOrValue::Value(None);
+            }
+        };
+
+        let withdrawal = match Self::verify_withdrawal_inclusion(
+            withdrawals_root,
+            withdrawal_position,
+            &withdrawal_data,
+            proof,
+        ) {
+            Some(withdrawal) => withdrawal,
+            None => return PromiseOrValue::Value(None),
+        };
+
+        if skip_bridge_call {
+            return PromiseOrValue::Value(Some(withdrawal));
+        }
+
+        // Verify block header was in the bridge
+        self.verify_proof_result_block_hash(&header, ProofKind::Withdrawal, withdrawal)
+            .into()
+    }
+
+    /// Verifies the account proof of `address` against the state root of the header.
+    /// Returns the decoded nonce, balance, storage root and code hash. The account which
+    /// doesn't exist at the block is returned with the empty state, and `None` is returned
+    /// if the proof or the address is invalid.
+    #[pause(except(roles(Role::UnrestrictedVerifyAccount, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_account(
+        &self,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] account_proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] address: Vec<u8>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Option<AccountState>> {
+        self.check_fee_paid(self.fee_schedule.account_proof_fee.0, skip_bridge_call);
+        let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
+        let account_state =
+            match Self::verify_account_inclusion(header.state_root, account_proof, &address) {
+                Some(account_state) => account_state,
+                None => return PromiseOrValue::Value(None),
+            };
+
+        if skip_bridge_call {
+            return PromiseOrValue::Value(Some(account_state));
+        }
+
+        // Verify block header was in the bridge
+        self.verify_proof_result_block_hash(&header, ProofKind::Account, account_state)
             .into()
     }
 
@@ -242,22 +982,21 @@ impl EthProver {
         #[serializer(borsh)] max_header_height: Option<u64>,
         #[serializer(borsh)] skip_bridge_call: bool,
     ) -> PromiseOrValue<bool> {
+        self.check_fee_paid(self.fee_schedule.storage_proof_fee.0, skip_bridge_call);
         let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
         if !Self::is_block_height_in_bound(header.number, min_header_height, max_header_height) {
             return PromiseOrValue::Value(false);
         }
 
-        let account_key = near_keccak256(&contract_address).to_vec();
-        let account_state = Self::verify_trie_proof(header.state_root, account_key, account_proof);
-        if account_state != expected_account_state {
-            env::log_str("account_state != expected_account_state");
-            return PromiseOrValue::Value(false);
-        }
-
-        let storage_hash: H256 = Rlp::new(&account_state).val_at(2).unwrap();
-        let storage_value = Self::verify_trie_proof(storage_hash, storage_key_hash, storage_proof);
-        if storage_value != expected_storage_value {
-            env::log_str("storage_value != expected_storage_value");
+        if !Self::verify_storage_proof_inclusion(
+            header.state_root,
+            account_proof,
+            &contract_address,
+            &expected_account_state,
+            storage_key_hash,
+            storage_proof,
+            &expected_storage_value,
+        ) {
             return PromiseOrValue::Value(false);
         }
 
@@ -266,13 +1005,106 @@ impl EthProver {
         }
 
         // Verify block header was in the bridge
+        self.verify_block_hash(&header, None, ProofKind::StorageProof)
+    }
+
+    /// Same as `verify_storage_proof`, but verifies several storage slots of the account
+    /// against a single account proof. Returns the result for each of `storage_slot_proofs`.
+    #[pause(except(roles(Role::UnrestrictedVerifyStorageProof, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_storage_proofs(
+        &self,
+        #[serializer(borsh)] header_data: Vec<u8>,
+        #[serializer(borsh)] account_proof: Vec<Vec<u8>>,
+        #[serializer(borsh)] contract_address: Vec<u8>,
+        #[serializer(borsh)] expected_account_state: Vec<u8>,
+        #[serializer(borsh)] storage_slot_proofs: Vec<StorageSlotProof>,
+        #[serializer(borsh)] skip_bridge_call: bool,
+    ) -> PromiseOrValue<Vec<bool>> {
+        require!(
+            !storage_slot_proofs.is_empty(),
+            "No storage slots to verify"
+        );
+        require!(
+            storage_slot_proofs.len() <= MAX_STORAGE_SLOTS_PER_BATCH,
+            format!(
+                "The list of storage slots is too long, max {} storage slots are allowed",
+                MAX_STORAGE_SLOTS_PER_BATCH
+            )
+        );
+        self.check_fee_paid(
+            self.fee_schedule.storage_proof_fee.0 * storage_slot_proofs.len() as u128,
+            skip_bridge_call,
+        );
+
+        let header: BlockHeader = rlp::decode(header_data.as_slice()).unwrap();
+        let storage_hash = match Self::verify_account_state(
+            header.state_root,
+            account_proof,
+            &contract_address,
+            &expected_account_state,
+        ) {
+            Some(storage_hash) => storage_hash,
+            None => return PromiseOrValue::Value(vec![false; storage_slot_proofs.len()]),
+        };
+
+        let slot_results = storage_slot_proofs
+            .into_iter()
+            .map(|slot_proof| {
+                Self::verify_storage_slot(
+                    storage_hash,
+                    slot_proof.storage_key_hash,
+                    slot_proof.storage_proof,
+                    &slot_proof.expected_storage_value,
+                )
+            })
+            .collect();
+
+        if skip_bridge_call {
+            return PromiseOrValue::Value(slot_results);
+        }
+
+        // Verify block header was in the bridge, none of the slots is proven if it wasn't
+        let failure_result = vec![false; slot_results.len()];
+        self.verify_result_block_hash(
+            &header,
+            ProofKind::StorageProof,
+            slot_results.try_to_vec().unwrap(),
+            failure_result.try_to_vec().unwrap(),
+        )
+        .into()
+    }
+
+    /// Same as `verify_storage_proof`, but instead of the header the state root is fetched
+    /// from the EthClient by `block_number`. Requires the EthClient to store the header roots.
+    #[pause(except(roles(Role::UnrestrictedVerifyStorageProof, Role::DAO)))]
+    #[result_serializer(borsh)]
+    pub fn verify_storage_proof_by_block_number(
+        &self,
+        #[serializer(borsh)] block_number: u64,
+        #[serializer(borsh)] account_proof: Vec<Vec<u8>>, // account proof
+        #[serializer(borsh)] contract_address: Vec<u8>,   // eth address
+        #[serializer(borsh)] expected_account_state: Vec<u8>, // encoded account state
+        #[serializer(borsh)] storage_key_hash: Vec<u8>,   // keccak256 of storage key
+        #[serializer(borsh)] storage_proof: Vec<Vec<u8>>, // storage proof
+        #[serializer(borsh)] expected_storage_value: Vec<u8>, // storage value
+    ) -> PromiseOrValue<bool> {
+        self.check_fee_paid(self.fee_schedule.storage_proof_fee.0, false);
         eth_client::ext(self.bridge_smart_contract.parse().unwrap())
-            .with_static_gas(BLOCK_HASH_SAFE_GAS)
-            .block_hash_safe(header.number)
+            .with_static_gas(HEADER_ROOTS_SAFE_GAS)
+            .header_roots_safe(block_number)
             .then(
                 remote_self::ext(env::current_account_id())
-                    .with_static_gas(ON_BLOCK_HASH_GAS)
-                    .on_block_hash(header.hash.unwrap()),
+                    .with_static_gas(ON_HEADER_ROOTS_GAS)
+                    .on_header_roots_for_storage_proof(
+                        block_number,
+                        account_proof,
+                        contract_address,
+                        expected_account_state,
+                        storage_key_hash,
+                        storage_proof,
+                        expected_storage_value,
+                    ),
             )
             .into()
     }
@@ -311,111 +1143,20 @@ impl EthProver {
         true
     }
 
-    /// Verify the proof recursively traversing through the key.
-    /// Return the value at the end of the key, in case the proof is valid.
-    ///
-    /// @param expected_root is the expected root of the current node.
-    /// @param key is the key for which we are proving the value.
-    /// @param proof contains relevant information to verify data is valid
-    ///
-    /// Patricia Trie: https://eth.wiki/en/fundamentals/patricia-tree
-    /// Patricia Img:  https://ethereum.stackexchange.com/questions/268/ethereum-block-architecture/6413#6413
-    ///
-    /// Verification:  https://github.com/slockit/in3/wiki/Ethereum-Verification-and-MerkleProof#receipt-proof
-    /// Article:       https://medium.com/@ouvrard.pierre.alain/merkle-proof-verification-for-ethereum-patricia-tree-48f29658eec
-    /// Python impl:   https://gist.github.com/mfornet/0ff283274c0162f1cca45966bccf69ee
-    ///
-    fn verify_trie_proof(expected_root: H256, key: Vec<u8>, proof: Vec<Vec<u8>>) -> Vec<u8> {
-        let mut actual_key = vec![];
-        for el in key {
-            actual_key.push(el / 16);
-            actual_key.push(el % 16);
-        }
-        Self::_verify_trie_proof((expected_root.0).0.into(), &actual_key, &proof, 0, 0)
-    }
-
-    fn _verify_trie_proof(
-        expected_root: Vec<u8>,
-        key: &Vec<u8>,
-        proof: &Vec<Vec<u8>>,
-        key_index: usize,
-        proof_index: usize,
-    ) -> Vec<u8> {
-        let node = &proof[proof_index];
-
-        if key_index == 0 {
-            // trie root is always a hash
-            assert_eq!(near_keccak256(node), expected_root.as_slice());
-        } else if node.len() < 32 {
-            // if rlp < 32 bytes, then it is not hashed
-            assert_eq!(node.as_slice(), expected_root);
-        } else {
-            assert_eq!(near_keccak256(node), expected_root.as_slice());
-        }
-
-        let node = Rlp::new(&node.as_slice());
-
-        if node.iter().count() == 17 {
-            // Branch node
-            if key_index >= key.len() {
-                assert_eq!(proof_index + 1, proof.len());
-                get_vec(&node, 16)
-            } else {
-                let new_expected_root = get_vec(&node, key[key_index] as usize);
-                if !new_expected_root.is_empty() {
-                    Self::_verify_trie_proof(
-                        new_expected_root,
-                        key,
-                        proof,
-                        key_index + 1,
-                        proof_index + 1,
-                    )
-                } else {
-                    // not included in proof
-                    vec![]
-                }
-            }
-        } else {
-            // Leaf or extension node
-            assert_eq!(node.iter().count(), 2);
-            let path_u8 = get_vec(&node, 0);
-            // Extract first nibble
-            let head = path_u8[0] / 16;
-            // assert!(0 <= head); is implicit because of type limits
-            assert!(head <= 3);
-
-            // Extract path
-            let mut path = vec![];
-            if head % 2 == 1 {
-                path.push(path_u8[0] % 16);
-            }
-            for val in path_u8.iter().skip(1) {
-                path.push(val / 16);
-                path.push(val % 16);
-            }
-
-            if head >= 2 {
-                // Leaf node
-                assert_eq!(proof_index + 1, proof.len());
-                assert_eq!(key_index + path.len(), key.len());
-                if path.as_slice() == &key[key_index..key_index + path.len()] {
-                    get_vec(&node, 1)
-                } else {
-                    vec![]
-                }
-            } else {
-                // Extension node
-                assert_eq!(path.as_slice(), &key[key_index..key_index + path.len()]);
-                let new_expected_root = get_vec(&node, 1);
-                Self::_verify_trie_proof(
-                    new_expected_root,
-                    key,
-                    proof,
-                    key_index + path.len(),
-                    proof_index + 1,
-                )
-            }
-        }
+    /// Verifies the Merkle-Patricia proof of `key` in the trie with the root `root`, e.g. the
+    /// state, storage, transactions, receipts or withdrawals trie.
+    /// Returns the value stored at `key`, the empty value if the proof shows that the key is
+    /// absent from the trie, or `None` if the proof is invalid.
+    #[result_serializer(borsh)]
+    pub fn verify_mpt_proof(
+        &self,
+        #[serializer(borsh)] root: H256,
+        #[serializer(borsh)] key: Vec<u8>,
+        #[serializer(borsh)] proof: Vec<Vec<u8>>,
+    ) -> Option<Vec<u8>> {
+        eth_mpt_proof::verify_proof(root, &key, &proof)
+            .ok()
+            .map(Option::unwrap_or_default)
     }
 
     #[access_control_any(roles(Role::DAO))]
@@ -430,6 +1171,74 @@ impl EthProver {
         self.bridge_smart_contract = bridge;
     }
 
+    #[access_control_any(roles(Role::DAO))]
+    pub fn update_fee_schedule(&mut self, fee_schedule: FeeSchedule) {
+        self.fee_schedule = fee_schedule;
+    }
+
+    pub fn get_fee_schedule(&self) -> FeeSchedule {
+        self.fee_schedule.clone()
+    }
+
+    pub fn get_accumulated_fees(&self) -> U128 {
+        U128(self.accumulated_fees)
+    }
+
+    /// Transfers the collected fees to the treasury account
+    #[access_control_any(roles(Role::DAO))]
+    pub fn withdraw_fees(&mut self, amount: U128, receiver_id: near_sdk::AccountId) -> Promise {
+        self.take_accumulated_fees(amount.0);
+        Promise::new(receiver_id).transfer(amount.0).then(
+            remote_self::ext(env::current_account_id())
+                .with_static_gas(ON_FEES_WITHDRAWN_GAS)
+                .on_fees_withdrawn(amount),
+        )
+    }
+
+    /// Deposits the collected fees to the relayers reward pool of the EthClient
+    #[access_control_any(roles(Role::DAO))]
+    pub fn withdraw_fees_to_reward_pool(&mut self, amount: U128) -> Promise {
+        self.take_accumulated_fees(amount.0);
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(DEPOSIT_REWARD_POOL_GAS)
+            .with_attached_deposit(amount.0)
+            .deposit_reward_pool()
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_FEES_WITHDRAWN_GAS)
+                    .on_fees_withdrawn(amount),
+            )
+    }
+
+    /// Returns the withdrawn amount back to the accumulated fees if the withdrawal failed
+    #[private]
+    pub fn on_fees_withdrawn(&mut self, amount: U128) {
+        if !is_promise_success() {
+            self.accumulated_fees += amount.0;
+        }
+    }
+
+    /// Charges the fee of the verification method `method_name` from the attached deposit
+    /// and calls it with the borsh-serialized `args`. The verification methods can't be
+    /// called directly by other accounts while the fee for them is set, unless
+    /// `skip_bridge_call` is used.
+    #[payable]
+    pub fn verify_with_fee(
+        &mut self,
+        #[serializer(borsh)] method_name: String,
+        #[serializer(borsh)] args: Vec<u8>,
+    ) -> Promise {
+        let fee = self.get_verification_fee(&method_name, &args);
+        self.charge_fee(fee);
+        Promise::new(env::current_account_id()).function_call_weight(
+            method_name,
+            args,
+            0,
+            Gas(0),
+            GasWeight(1),
+        )
+    }
+
     #[access_control_any(roles(Role::DAO))]
     pub fn attach_full_access_key(&self, public_key: PublicKey) -> Promise {
         Promise::new(env::current_account_id()).add_full_access_key(public_key)
@@ -440,6 +1249,370 @@ impl EthProver {
     }
 }
 
+impl EthProver {
+    /// Collects the fee from the attached deposit and refunds the rest to the predecessor
+    fn charge_fee(&mut self, fee: Balance) {
+        let attached_deposit = env::attached_deposit();
+        require!(
+            attached_deposit >= fee,
+            format!(
+                "The attached deposit should cover the verification fee of {} yoctoNEAR",
+                fee
+            )
+        );
+        self.accumulated_fees += fee;
+
+        let refund = attached_deposit - fee;
+        if refund > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+    }
+
+    /// Requires the fee to be paid through `verify_with_fee` for the calls relying on the EthClient
+    fn check_fee_paid(&self, fee: Balance, skip_bridge_call: bool) {
+        require!(
+            fee == 0
+                || skip_bridge_call
+                || env::predecessor_account_id() == env::current_account_id(),
+            "The verification fee is set, call the method through verify_with_fee"
+        );
+    }
+
+    /// Returns the fee of the verification method called with the borsh-serialized `args`
+    fn get_verification_fee(&self, method_name: &str, args: &[u8]) -> Balance {
+        match method_name {
+            "verify_log_entry"
+            | "verify_log_entry_with_result"
+            | "verify_log_entry_with_historical_proof"
+            | "verify_log_entry_with_block_roots_proof"
+            | "verify_log_entry_in_bound"
+            | "verify_log_entry_by_block_number" => self.fee_schedule.log_entry_fee.0,
+            "verify_log_entries" => {
+                let log_entry_proofs = Vec::<LogEntryProof>::deserialize(&mut &args[..])
+                    .unwrap_or_else(|_| env::panic_str("Invalid arguments of verify_log_entries"));
+                self.fee_schedule.log_entry_fee.0 * log_entry_proofs.len() as u128
+            }
+            "verify_transaction" => self.fee_schedule.transaction_fee.0,
+            "verify_withdrawal" => self.fee_schedule.withdrawal_fee.0,
+            "verify_account" => self.fee_schedule.account_proof_fee.0,
+            "verify_storage_proof" | "verify_storage_proof_by_block_number" => {
+                self.fee_schedule.storage_proof_fee.0
+            }
+            "verify_storage_proofs" => {
+                let (_, _, _, _, storage_slot_proofs) = <(
+                    Vec<u8>,
+                    Vec<Vec<u8>>,
+                    Vec<u8>,
+                    Vec<u8>,
+                    Vec<StorageSlotProof>,
+                )>::deserialize(
+                    &mut &args[..]
+                )
+                .unwrap_or_else(|_| env::panic_str("Invalid arguments of verify_storage_proofs"));
+                self.fee_schedule.storage_proof_fee.0 * storage_slot_proofs.len() as u128
+            }
+            _ => env::panic_str(&format!("Unknown verification method {}", method_name)),
+        }
+    }
+
+    /// Checks the block hash of the header against the EthClient and returns `Some(result)`
+    /// from `on_proof_result_block_hash` if it matches, otherwise `None`
+    fn verify_proof_result_block_hash<T: BorshSerialize>(
+        &self,
+        header: &BlockHeader,
+        proof_kind: ProofKind,
+        result: T,
+    ) -> Promise {
+        self.verify_result_block_hash(
+            header,
+            proof_kind,
+            Some(result).try_to_vec().unwrap(),
+            None::<T>.try_to_vec().unwrap(),
+        )
+    }
+
+    /// Checks the block hash of the header against the EthClient and returns the borsh-serialized
+    /// `result` from `on_proof_result_block_hash` if it matches, otherwise `failure_result`
+    fn verify_result_block_hash(
+        &self,
+        header: &BlockHeader,
+        proof_kind: ProofKind,
+        result: Vec<u8>,
+        failure_result: Vec<u8>,
+    ) -> Promise {
+        eth_client::ext(self.bridge_smart_contract.parse().unwrap())
+            .with_static_gas(BLOCK_HASH_SAFE_GAS)
+            .block_hash_safe(header.number)
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_BLOCK_HASH_GAS)
+                    .on_proof_result_block_hash(
+                        header.hash.unwrap(),
+                        header.number,
+                        proof_kind,
+                        result,
+                        failure_result,
+                    ),
+            )
+    }
+
+    fn take_accumulated_fees(&mut self, amount: Balance) {
+        require!(
+            amount <= self.accumulated_fees,
+            "The amount exceeds the accumulated fees"
+        );
+        self.accumulated_fees -= amount;
+    }
+
+    /// Verifies that the log entry is included into the receipt and the receipt is included
+    /// into the receipts trie with the root `receipts_root`.
+    fn verify_log_entry_inclusion(
+        receipts_root: H256,
+        log_index: u64,
+        log_entry_data: &[u8],
+        receipt_index: u64,
+        receipt_data: &[u8],
+        proof: Vec<Vec<u8>>,
+    ) -> bool {
+        let log_entry: LogEntry = rlp::decode(log_entry_data).unwrap();
+        let receipt: Receipt = rlp::decode(receipt_data).unwrap();
+
+        // Verify log_entry included in receipt
+        let log_index_usize = usize::try_from(log_index).expect("Invalid log_index");
+        assert_eq!(receipt.logs[log_index_usize], log_entry);
+
+        // Verify receipt included into header
+        let data = eth_mpt_proof::verify_proof(receipts_root, &rlp::encode(&receipt_index), &proof)
+            .unwrap_or_else(|e| env::panic_str(format!("Invalid receipt proof: {}", e).as_str()));
+        data.as_deref() == Some(receipt_data)
+    }
+
+    /// Same as `verify_log_entry_inclusion`, but returns the proven log entry or the reason
+    /// why the proof is invalid instead of panicking on the log entry mismatch.
+    fn verify_log_entry_details(
+        header: &BlockHeader,
+        log_index: u64,
+        log_entry_data: &[u8],
+        receipt_index: u64,
+        receipt_data: &[u8],
+        proof: Vec<Vec<u8>>,
+        event_signature: Option<&str>,
+    ) -> Result<VerifiedLogEntry, LogEntryVerificationError> {
+        let log_entry: LogEntry =
+            rlp::decode(log_entry_data).map_err(|_| LogEntryVerificationError::LogEntryMismatch)?;
+        let receipt: Receipt = rlp::decode(receipt_data)
+            .map_err(|_| LogEntryVerificationError::InvalidReceiptProof)?;
+
+        let receipt_log_entry = usize::try_from(log_index)
+            .ok()
+            .and_then(|log_index| receipt.logs.get(log_index));
+        if receipt_log_entry != Some(&log_entry) {
+            return Err(LogEntryVerificationError::LogEntryMismatch);
+        }
+
+        let data =
+            eth_mpt_proof::verify_proof(header.receipts_root, &rlp::encode(&receipt_index), &proof);
+        if data.ok().flatten().as_deref() != Some(receipt_data) {
+            return Err(LogEntryVerificationError::InvalidReceiptProof);
+        }
+
+        let decoded_params = event_signature
+            .map(|event_signature| {
+                EventSignature::parse(event_signature)?
+                    .decode_log(&log_entry.topics, &log_entry.data)
+            })
+            .transpose()
+            .map_err(LogEntryVerificationError::Abi)?;
+
+        Ok(VerifiedLogEntry {
+            address: log_entry.address,
+            topics: log_entry.topics,
+            data: log_entry.data,
+            block_number: header.number,
+            block_hash: header.hash.unwrap(),
+            receipt_status: receipt.status,
+            decoded_params,
+        })
+    }
+
+    /// Verifies that the transaction is included into the transactions trie with the root
+    /// `transactions_root` and decodes it.
+    fn verify_transaction_inclusion(
+        transactions_root: H256,
+        tx_index: u64,
+        tx_data: &[u8],
+        proof: Vec<Vec<u8>>,
+    ) -> Option<Transaction> {
+        let data = eth_mpt_proof::verify_proof(transactions_root, &rlp::encode(&tx_index), &proof);
+        if data.ok().flatten().as_deref() != Some(tx_data) {
+            env::log_str("The transaction is not included into the transactions trie");
+            return None;
+        }
+
+        Some(
+            decode_transaction(tx_data)
+                .unwrap_or_else(|e| env::panic_str(format!("Invalid transaction: {}", e).as_str())),
+        )
+    }
+
+    /// Verifies that the withdrawal is included into the withdrawals trie with the root
+    /// `withdrawals_root` and decodes it.
+    fn verify_withdrawal_inclusion(
+        withdrawals_root: H256,
+        withdrawal_position: u64,
+        withdrawal_data: &[u8],
+        proof: Vec<Vec<u8>>,
+    ) -> Option<Withdrawal> {
+        let data = eth_mpt_proof::verify_proof(
+            withdrawals_root,
+            &rlp::encode(&withdrawal_position),
+            &proof,
+        );
+        if data.ok().flatten().as_deref() != Some(withdrawal_data) {
+            env::log_str("The withdrawal is not included into the withdrawals trie");
+            return None;
+        }
+
+        Some(
+            rlp::decode(withdrawal_data)
+                .unwrap_or_else(|e| env::panic_str(format!("Invalid withdrawal: {}", e).as_str())),
+        )
+    }
+
+    /// Verifies the account proof against the state trie with the root `state_root` and
+    /// decodes the account state.
+    fn verify_account_inclusion(
+        state_root: H256,
+        account_proof: Vec<Vec<u8>>,
+        address: &[u8],
+    ) -> Option<AccountState> {
+        if address.len() != 20 {
+            env::log_str("Invalid address length");
+            return None;
+        }
+
+        let account_key = near_keccak256(address);
+        let account_state =
+            match eth_mpt_proof::verify_proof(state_root, &account_key, &account_proof) {
+                Ok(Some(account_state)) => account_state,
+                Ok(None) => return Some(AccountState::empty()),
+                Err(e) => {
+                    env::log_str(format!("Invalid account proof: {}", e).as_str());
+                    return None;
+                }
+            };
+
+        Some(
+            rlp::decode(&account_state).unwrap_or_else(|e| {
+                env::panic_str(format!("Invalid account state: {}", e).as_str())
+            }),
+        )
+    }
+
+    /// Verifies the account state against the state trie with the root `state_root` and
+    /// the storage value against the storage trie of this account.
+    fn verify_storage_proof_inclusion(
+        state_root: H256,
+        account_proof: Vec<Vec<u8>>,
+        contract_address: &[u8],
+        expected_account_state: &[u8],
+        storage_key_hash: Vec<u8>,
+        storage_proof: Vec<Vec<u8>>,
+        expected_storage_value: &[u8],
+    ) -> bool {
+        match Self::verify_account_state(
+            state_root,
+            account_proof,
+            contract_address,
+            expected_account_state,
+        ) {
+            Some(storage_hash) => Self::verify_storage_slot(
+                storage_hash,
+                storage_key_hash,
+                storage_proof,
+                expected_storage_value,
+            ),
+            None => false,
+        }
+    }
+
+    /// Verifies the account state against the state trie with the root `state_root`.
+    /// Returns the storage root of the account if the state matches.
+    fn verify_account_state(
+        state_root: H256,
+        account_proof: Vec<Vec<u8>>,
+        contract_address: &[u8],
+        expected_account_state: &[u8],
+    ) -> Option<H256> {
+        let account_key = near_keccak256(contract_address);
+        let account_state = eth_mpt_proof::verify_proof(state_root, &account_key, &account_proof)
+            .unwrap_or_else(|e| env::panic_str(format!("Invalid account proof: {}", e).as_str()))
+            .unwrap_or_default();
+        if account_state != expected_account_state {
+            env::log_str("account_state != expected_account_state");
+            return None;
+        }
+
+        Some(Rlp::new(&account_state).val_at(2).unwrap())
+    }
+
+    /// Verifies the storage value against the storage trie with the root `storage_hash`
+    fn verify_storage_slot(
+        storage_hash: H256,
+        storage_key_hash: Vec<u8>,
+        storage_proof: Vec<Vec<u8>>,
+        expected_storage_value: &[u8],
+    ) -> bool {
+        let storage_value =
+            eth_mpt_proof::verify_proof(storage_hash, &storage_key_hash, &storage_proof)
+                .unwrap_or_else(|e| {
+                    env::panic_str(format!("Invalid storage proof: {}", e).as_str())
+                })
+                .unwrap_or_default();
+        if storage_value != expected_storage_value {
+            env::log_str("storage_value != expected_storage_value");
+            return false;
+        }
+
+        true
+    }
+
+    /// Verifies that the header is part of the canonical chain known to the bridge.
+    /// Uses `block_hash_proof` if provided, otherwise looks the header up by its number.
+    fn verify_block_hash(
+        &self,
+        header: &BlockHeader,
+        block_hash_proof: Option<BlockHashProof>,
+        proof_kind: ProofKind,
+    ) -> PromiseOrValue<bool> {
+        let bridge_account_id = self.bridge_smart_contract.parse().unwrap();
+        let block_hash_promise = match block_hash_proof {
+            Some(block_hash_proof) => Promise::new(bridge_account_id).function_call(
+                block_hash_proof.method_name().to_string(),
+                block_hash_proof.into_args(),
+                0,
+                BLOCK_HASH_PROOF_GAS,
+            ),
+            None => eth_client::ext(bridge_account_id)
+                .with_static_gas(BLOCK_HASH_SAFE_GAS)
+                .block_hash_safe(header.number),
+        };
+
+        block_hash_promise
+            .then(
+                remote_self::ext(env::current_account_id())
+                    .with_static_gas(ON_BLOCK_HASH_GAS)
+                    .on_proof_block_hash(header.hash.unwrap(), header.number, proof_kind),
+            )
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod test_utils;
 #[cfg(test)]
 mod tests;
+mod tests_abi;
 mod tests_storage_proof;
+mod tests_transaction;
+mod tests_withdrawal;
@@ -0,0 +1,24 @@
+use crate::*;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct EthProverV1 {
+    bridge_smart_contract: AccountId,
+    #[deprecated]
+    paused: u128,
+}
+
+#[near_bindgen]
+impl EthProver {
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old_state: EthProverV1 = env::state_read().expect("failed");
+        #[allow(deprecated)]
+        Self {
+            bridge_smart_contract: old_state.bridge_smart_contract,
+            paused: old_state.paused,
+            fee_schedule: FeeSchedule::default(),
+            accumulated_fees: 0,
+        }
+    }
+}
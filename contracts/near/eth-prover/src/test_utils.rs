@@ -0,0 +1,47 @@
+use eth_types::{near_keccak256, H256};
+use near_sdk::VMContext;
+use rlp::RlpStream;
+
+pub fn get_context(input: Vec<u8>) -> VMContext {
+    VMContext {
+        current_account_id: "alice.near".parse().unwrap(),
+        signer_account_id: "bob.near".parse().unwrap(),
+        signer_account_pk: "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+            .parse()
+            .unwrap(),
+        predecessor_account_id: "carol.near".parse().unwrap(),
+        input,
+        block_index: 0,
+        block_timestamp: 0,
+        epoch_height: 0,
+        account_balance: 0,
+        account_locked_balance: 0,
+        storage_usage: 0,
+        attached_deposit: 0,
+        prepaid_gas: near_sdk::Gas(10u64.pow(18)),
+        random_seed: vec![1; 32].try_into().unwrap(),
+        view_config: None,
+        output_data_receivers: vec![],
+    }
+}
+
+/// Builds the trie with the RLP-encoded `index` key containing only the given value.
+/// Returns the trie root and the proof of the value.
+pub fn get_single_leaf_trie(index: u64, value: &[u8]) -> (H256, Vec<Vec<u8>>) {
+    get_single_key_trie(&rlp::encode(&index), value)
+}
+
+/// Builds the trie with the given `key` containing only the given value.
+/// Returns the trie root and the proof of the value.
+pub fn get_single_key_trie(key: &[u8], value: &[u8]) -> (H256, Vec<Vec<u8>>) {
+    // Leaf node with the even-length path
+    let mut path = vec![0x20];
+    path.extend_from_slice(key);
+
+    let mut leaf = RlpStream::new_list(2);
+    leaf.append(&path);
+    leaf.append(&value.to_vec());
+    let leaf = leaf.out().to_vec();
+
+    (H256(near_keccak256(&leaf).into()), vec![leaf])
+}
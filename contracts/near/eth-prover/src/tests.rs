@@ -1,10 +1,19 @@
 #[cfg(test)]
 mod tests {
-    use crate::EthProver;
+    use crate::abi::{AbiError, AbiValue};
+    use crate::events::{EthProverEvent, ProofKind};
+    use crate::{
+        EthProver, LogEntryProof, LogEntryVerificationError, LogEntryVerificationResult,
+        VerifiedLogEntry, BLOCK_HASH_PROOF_GAS, HEADER_ROOTS_SAFE_GAS, MAX_LOG_ENTRIES_PER_BATCH,
+    };
+    use eth_types::events::NearEvent;
+    use eth_types::{BlockHeader, BlockHeaderRoots, H160, H256, U256};
     use hex::{FromHex, ToHex};
-    use near_sdk::borsh;
+    use near_sdk::borsh::{self, BorshSerialize};
+    use near_sdk::mock::VmAction;
+    use near_sdk::test_utils::{get_created_receipts, get_logs};
     use near_sdk::PromiseOrValue;
-    use rlp::RlpStream;
+    use rlp::{Rlp, RlpStream};
     use serde::{Deserialize, Deserializer};
 
     #[derive(Debug)]
@@ -97,6 +106,531 @@ mod tests {
         }
     }
 
+    fn get_simple_tx_log_entry_proof() -> LogEntryProof {
+        // Following data could be extracted by:
+        // NODE_URL="https://mainnet.infura.io/v3/b5f870422ee5454fb11937e947154cd2" TX_HASH="0xb540248a9cca048c5861dec953d7a776bc1944319b9bd27a462469c8a437f4ff" EVENT_INDEX=0 node extract.js
+
+        let log_index = 0;
+        let receipt_index = 0;
+        let header_data = Vec::from_hex("f9021aa0f779e50b45bc27e4ed236840e5dbcf7afab50beaf553be56bf76da977e10cc73a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d493479452bc44d5378309ee2abf1539bf71de1b7d7be3b5a014c996b6934d7991643669e145b8355c63aa02cbde63d390fcf4e6181d5eea45a079b7e79dc739c31662fe6f25f65bf5a5d14299c7a7aa42c3f75b9fb05474f54ca0e28dc05418692cb7baab7e7f85c1dedb8791c275b797ea3b1ffcaec5ef2aa271b9010000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000010000000000000000000000000000000000000000000000000000000408000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000010000000000000000000000000000000000000000000000000000000400000000000100000000000000000000000000080000000000000000000000000000000000000000000100002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000903234373439353837313930323034343383890fe68395ba8e82d0d9845dd84a079150505945206e616e6f706f6f6c2e6f7267a0a35425f443452cf94ba4b698b00fd7b3ff4fc671dea3d5cc2dcbedbc3766f45e88af7fec6031063a17").unwrap();
+        let receipt_data = Vec::from_hex("f901a60182d0d9b9010000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000010000000000000000000000000000000000000000000000000000000408000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000010000000000000000000000000000000000000000000000000000000400000000000100000000000000000000000000080000000000000000000000000000000000000000000100002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000f89df89b94dac17f958d2ee523a2206206994597c13d831ec7f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000006cc5f688a315f3dc28a7781717a9a798a59fda7ba00000000000000000000000007e7a32d9dc98c485c489be8e732f97b4ffe3a4cda000000000000000000000000000000000000000000000000000000001a13b8600").unwrap();
+        let log_entry = Vec::from_hex("f89b94dac17f958d2ee523a2206206994597c13d831ec7f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000006cc5f688a315f3dc28a7781717a9a798a59fda7ba00000000000000000000000007e7a32d9dc98c485c489be8e732f97b4ffe3a4cda000000000000000000000000000000000000000000000000000000001a13b8600").unwrap();
+        let proof = vec![
+        vec![
+            Vec::from_hex("2080").unwrap(),
+            Vec::from_hex("f901a60182d0d9b9010000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000010000000000000000000000000000000000000000000000000000000408000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000010000000000000000000000000000000000000000000000000000000400000000000100000000000000000000000000080000000000000000000000000000000000000000000100002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000f89df89b94dac17f958d2ee523a2206206994597c13d831ec7f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000006cc5f688a315f3dc28a7781717a9a798a59fda7ba00000000000000000000000007e7a32d9dc98c485c489be8e732f97b4ffe3a4cda000000000000000000000000000000000000000000000000000000001a13b8600").unwrap(),
+        ],
+    ].iter().map(|node| {
+        let mut stream = RlpStream::new();
+        stream.begin_list(node.len());
+        for item in node {
+            stream.append(item);
+        }
+        stream.out().to_vec()
+    }).collect();
+
+        LogEntryProof {
+            log_index,
+            log_entry_data: log_entry,
+            receipt_index,
+            receipt_data,
+            header_data,
+            proof,
+        }
+    }
+
+    #[test]
+    fn verify_log_entries_from_same_block() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let log_entry_proofs = vec![
+            get_simple_tx_log_entry_proof(),
+            get_simple_tx_log_entry_proof(),
+        ];
+
+        if let PromiseOrValue::Value(true) = contract.verify_log_entries(log_entry_proofs, true) {
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn verify_log_entries_from_different_headers() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let log_entry_proof = get_simple_tx_log_entry_proof();
+        let header: BlockHeader = rlp::decode(&log_entry_proof.header_data).unwrap();
+
+        // The header of the same block with the different extra data
+        let header_rlp = Rlp::new(&log_entry_proof.header_data);
+        let mut stream = RlpStream::new_list(header_rlp.item_count().unwrap());
+        for (i, field) in header_rlp.iter().enumerate() {
+            if i == 12 {
+                stream.append(&vec![0u8]);
+            } else {
+                stream.append_raw(field.as_raw(), 1);
+            }
+        }
+        let other_log_entry_proof = LogEntryProof {
+            header_data: stream.out().to_vec(),
+            ..get_simple_tx_log_entry_proof()
+        };
+
+        if let PromiseOrValue::Value(false) =
+            contract.verify_log_entries(vec![log_entry_proof, other_log_entry_proof], true)
+        {
+        } else {
+            panic!();
+        }
+        assert_eq!(
+            get_logs(),
+            vec![format!(
+                "Different headers are provided for block {}",
+                header.number
+            )]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The list of log entries is too long")]
+    fn verify_log_entries_with_too_many_proofs() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let log_entry_proofs = (0..=MAX_LOG_ENTRIES_PER_BATCH)
+            .map(|_| get_simple_tx_log_entry_proof())
+            .collect();
+        contract.verify_log_entries(log_entry_proofs, true);
+    }
+
+    #[test]
+    fn on_block_hashes() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let expected_block_hashes = vec![H256::from([1u8; 32]), H256::from([2u8; 32])];
+        assert!(contract.on_block_hashes(
+            expected_block_hashes.iter().cloned().map(Some).collect(),
+            vec![100, 101],
+            expected_block_hashes.clone(),
+        ));
+
+        let expected_logs: Vec<String> = [100, 101]
+            .into_iter()
+            .map(|block_number| {
+                let event = EthProverEvent::ProofVerified {
+                    proof_kind: ProofKind::LogEntry,
+                    block_number,
+                };
+                format!("EVENT_JSON:{}", event.to_json_string())
+            })
+            .collect();
+        assert_eq!(get_logs(), expected_logs);
+
+        // Nothing is proven if any of the block hashes doesn't match
+        testing_env!(get_context(vec![]));
+        assert!(!contract.on_block_hashes(
+            vec![Some(expected_block_hashes[0]), None],
+            vec![100, 101],
+            expected_block_hashes.clone(),
+        ));
+        assert!(!contract.on_block_hashes(
+            vec![
+                Some(expected_block_hashes[0]),
+                Some(expected_block_hashes[0])
+            ],
+            vec![100, 101],
+            expected_block_hashes.clone(),
+        ));
+        assert!(!contract.on_block_hashes(
+            vec![Some(expected_block_hashes[0])],
+            vec![100, 101],
+            expected_block_hashes,
+        ));
+        assert!(get_logs().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "No log entries to verify")]
+    fn verify_log_entries_without_proofs() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        contract.verify_log_entries(vec![], true);
+    }
+
+    fn verify_simple_tx_log_entry_with_result(
+        log_entry_proof: LogEntryProof,
+        event_signature: Option<&str>,
+    ) -> LogEntryVerificationResult {
+        let contract = EthProver::init("ethbridge".to_string());
+        match contract.verify_log_entry_with_result(
+            log_entry_proof.log_index,
+            log_entry_proof.log_entry_data,
+            log_entry_proof.receipt_index,
+            log_entry_proof.receipt_data,
+            log_entry_proof.header_data,
+            log_entry_proof.proof,
+            event_signature.map(|event_signature| event_signature.to_string()),
+            true,
+        ) {
+            PromiseOrValue::Value(result) => result,
+            PromiseOrValue::Promise(_) => panic!(),
+        }
+    }
+
+    fn get_simple_tx_verified_log_entry() -> VerifiedLogEntry {
+        match verify_simple_tx_log_entry_with_result(get_simple_tx_log_entry_proof(), None) {
+            LogEntryVerificationResult::Verified(verified_log_entry) => verified_log_entry,
+            LogEntryVerificationResult::Failed(err) => panic!("{:?}", err),
+        }
+    }
+
+    fn address(hex: &str) -> H160 {
+        H160::from(&Vec::from_hex(hex).unwrap())
+    }
+
+    #[test]
+    fn verify_log_entry_with_result() {
+        testing_env!(get_context(vec![]));
+
+        let verified_log_entry = match verify_simple_tx_log_entry_with_result(
+            get_simple_tx_log_entry_proof(),
+            Some("Transfer(address indexed from, address indexed to, uint256 value)"),
+        ) {
+            LogEntryVerificationResult::Verified(verified_log_entry) => verified_log_entry,
+            LogEntryVerificationResult::Failed(err) => panic!("{:?}", err),
+        };
+
+        assert_eq!(
+            verified_log_entry.address,
+            address("dac17f958d2ee523a2206206994597c13d831ec7")
+        );
+        assert_eq!(verified_log_entry.block_number, 8_982_502);
+        assert!(verified_log_entry.receipt_status);
+        assert_eq!(verified_log_entry.topics.len(), 3);
+        assert_eq!(
+            verified_log_entry.decoded_params,
+            Some(vec![
+                AbiValue::Address(address("6cc5f688a315f3dc28a7781717a9a798a59fda7b")),
+                AbiValue::Address(address("7e7a32d9dc98c485c489be8e732f97b4ffe3a4cd")),
+                AbiValue::Uint(U256(7_000_000_000u64.into())),
+            ])
+        );
+    }
+
+    #[test]
+    fn verify_log_entry_with_result_failures() {
+        testing_env!(get_context(vec![]));
+
+        let mut log_entry_proof = get_simple_tx_log_entry_proof();
+        log_entry_proof.log_index = 1;
+        assert_eq!(
+            verify_simple_tx_log_entry_with_result(log_entry_proof, None),
+            LogEntryVerificationResult::Failed(LogEntryVerificationError::LogEntryMismatch)
+        );
+
+        let mut log_entry_proof = get_simple_tx_log_entry_proof();
+        log_entry_proof.proof[0][0] ^= 0x01;
+        assert_eq!(
+            verify_simple_tx_log_entry_with_result(log_entry_proof, None),
+            LogEntryVerificationResult::Failed(LogEntryVerificationError::InvalidReceiptProof)
+        );
+
+        let mut log_entry_proof = get_simple_tx_log_entry_proof();
+        log_entry_proof.log_entry_data = vec![0xff];
+        assert_eq!(
+            verify_simple_tx_log_entry_with_result(log_entry_proof, None),
+            LogEntryVerificationResult::Failed(LogEntryVerificationError::LogEntryMismatch)
+        );
+
+        let mut log_entry_proof = get_simple_tx_log_entry_proof();
+        log_entry_proof.receipt_data = vec![0xff];
+        assert_eq!(
+            verify_simple_tx_log_entry_with_result(log_entry_proof, None),
+            LogEntryVerificationResult::Failed(LogEntryVerificationError::InvalidReceiptProof)
+        );
+
+        assert_eq!(
+            verify_simple_tx_log_entry_with_result(
+                get_simple_tx_log_entry_proof(),
+                Some("Approval(address indexed owner, address indexed spender, uint256 value)"),
+            ),
+            LogEntryVerificationResult::Failed(LogEntryVerificationError::Abi(
+                AbiError::SignatureMismatch
+            ))
+        );
+    }
+
+    #[test]
+    fn on_log_entry_block_hash() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let verified_log_entry = get_simple_tx_verified_log_entry();
+        let block_hash = verified_log_entry.block_hash;
+        let block_number = verified_log_entry.block_number;
+
+        assert_eq!(
+            contract.on_log_entry_block_hash(
+                Some(block_hash),
+                block_number,
+                verified_log_entry.clone()
+            ),
+            LogEntryVerificationResult::Verified(verified_log_entry.clone())
+        );
+        assert_eq!(
+            contract.on_log_entry_block_hash(
+                Some(H256::from([0x01; 32])),
+                block_number,
+                verified_log_entry.clone()
+            ),
+            LogEntryVerificationResult::Failed(LogEntryVerificationError::BlockHashMismatch)
+        );
+        assert_eq!(
+            contract.on_log_entry_block_hash(None, block_number - 1, verified_log_entry.clone()),
+            LogEntryVerificationResult::Failed(LogEntryVerificationError::BlockNotFinalized)
+        );
+        assert_eq!(
+            contract.on_log_entry_block_hash(None, block_number + 100, verified_log_entry),
+            LogEntryVerificationResult::Failed(LogEntryVerificationError::BlockGarbageCollected)
+        );
+    }
+
+    #[test]
+    fn verify_log_entry_with_historical_proof() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        // The proof is passed to the EthClient as is
+        let historical_block_proof = vec![0x01, 0x02, 0x03];
+        let log_entry_proof = get_simple_tx_log_entry_proof();
+        let header: BlockHeader = rlp::decode(&log_entry_proof.header_data).unwrap();
+        if let PromiseOrValue::Value(_) = contract.verify_log_entry_with_historical_proof(
+            log_entry_proof.log_index,
+            log_entry_proof.log_entry_data,
+            log_entry_proof.receipt_index,
+            log_entry_proof.receipt_data,
+            log_entry_proof.header_data,
+            log_entry_proof.proof,
+            historical_block_proof.clone(),
+        ) {
+            panic!();
+        }
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].receiver_id.as_str(), "ethbridge");
+        assert_eq!(
+            receipts[0].actions[0],
+            VmAction::FunctionCall {
+                function_name: "historical_block_hash_safe".to_string(),
+                args: historical_block_proof,
+                gas: BLOCK_HASH_PROOF_GAS,
+                deposit: 0,
+            }
+        );
+        match &receipts[1].actions[0] {
+            VmAction::FunctionCall {
+                function_name,
+                args,
+                ..
+            } => {
+                assert_eq!(function_name, "on_proof_block_hash");
+                assert_eq!(
+                    args,
+                    &(header.hash.unwrap(), header.number, ProofKind::LogEntry)
+                        .try_to_vec()
+                        .unwrap()
+                );
+            }
+            action => panic!("{:?}", action),
+        }
+    }
+
+    #[test]
+    fn verify_log_entry_by_block_number() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let log_entry_proof = get_simple_tx_log_entry_proof();
+        let block_number = 8_982_502u64;
+        if let PromiseOrValue::Value(_) = contract.verify_log_entry_by_block_number(
+            log_entry_proof.log_index,
+            log_entry_proof.log_entry_data,
+            log_entry_proof.receipt_index,
+            log_entry_proof.receipt_data,
+            block_number,
+            log_entry_proof.proof,
+        ) {
+            panic!();
+        }
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].receiver_id.as_str(), "ethbridge");
+        assert_eq!(
+            receipts[0].actions[0],
+            VmAction::FunctionCall {
+                function_name: "header_roots_safe".to_string(),
+                args: block_number.try_to_vec().unwrap(),
+                gas: HEADER_ROOTS_SAFE_GAS,
+                deposit: 0,
+            }
+        );
+        assert!(matches!(
+            &receipts[1].actions[0],
+            VmAction::FunctionCall { function_name, .. }
+                if function_name == "on_header_roots_for_log_entry"
+        ));
+    }
+
+    #[test]
+    fn on_header_roots_for_log_entry() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let log_entry_proof = get_simple_tx_log_entry_proof();
+        let header: BlockHeader = rlp::decode(&log_entry_proof.header_data).unwrap();
+        assert!(contract.on_header_roots_for_log_entry(
+            Some(BlockHeaderRoots::from(&header)),
+            header.number,
+            log_entry_proof.log_index,
+            log_entry_proof.log_entry_data.clone(),
+            log_entry_proof.receipt_index,
+            log_entry_proof.receipt_data.clone(),
+            log_entry_proof.proof.clone(),
+        ));
+
+        let expected_event = EthProverEvent::ProofVerified {
+            proof_kind: ProofKind::LogEntry,
+            block_number: header.number,
+        };
+        assert_eq!(
+            get_logs(),
+            vec![format!("EVENT_JSON:{}", expected_event.to_json_string())]
+        );
+
+        // The header roots are not stored by the EthClient
+        assert!(!contract.on_header_roots_for_log_entry(
+            None,
+            header.number,
+            log_entry_proof.log_index,
+            log_entry_proof.log_entry_data,
+            log_entry_proof.receipt_index,
+            log_entry_proof.receipt_data,
+            log_entry_proof.proof,
+        ));
+    }
+
+    #[test]
+    fn on_proof_block_hash() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let expected_block_hash = H256::from([1u8; 32]);
+        assert!(contract.on_proof_block_hash(
+            Some(expected_block_hash),
+            expected_block_hash,
+            100,
+            ProofKind::Account,
+        ));
+
+        let expected_event = EthProverEvent::ProofVerified {
+            proof_kind: ProofKind::Account,
+            block_number: 100,
+        };
+        assert_eq!(
+            get_logs(),
+            vec![format!("EVENT_JSON:{}", expected_event.to_json_string())]
+        );
+
+        // No event is emitted if the block hash doesn't match
+        testing_env!(get_context(vec![]));
+        assert!(!contract.on_proof_block_hash(
+            Some(H256::from([2u8; 32])),
+            expected_block_hash,
+            100,
+            ProofKind::Account,
+        ));
+        assert!(!contract.on_proof_block_hash(None, expected_block_hash, 100, ProofKind::Account));
+        assert!(get_logs().is_empty());
+    }
+
+    #[test]
+    fn on_proof_result_block_hash() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let expected_block_hash = H256::from([1u8; 32]);
+        contract.on_proof_result_block_hash(
+            Some(expected_block_hash),
+            expected_block_hash,
+            100,
+            ProofKind::Withdrawal,
+            vec![1, 2],
+            vec![0],
+        );
+
+        let expected_event = EthProverEvent::ProofVerified {
+            proof_kind: ProofKind::Withdrawal,
+            block_number: 100,
+        };
+        assert_eq!(
+            get_logs(),
+            vec![format!("EVENT_JSON:{}", expected_event.to_json_string())]
+        );
+
+        testing_env!(get_context(vec![]));
+        contract.on_proof_result_block_hash(
+            None,
+            expected_block_hash,
+            100,
+            ProofKind::Withdrawal,
+            vec![1, 2],
+            vec![0],
+        );
+        assert!(get_logs().is_empty());
+    }
+
+    #[test]
+    fn verify_mpt_proof() {
+        testing_env!(get_context(vec![]));
+
+        let contract = EthProver::init("ethbridge".to_string());
+        let log_entry_proof = get_simple_tx_log_entry_proof();
+        let header: BlockHeader = rlp::decode(&log_entry_proof.header_data).unwrap();
+        let receipt_key = rlp::encode(&log_entry_proof.receipt_index).to_vec();
+
+        assert_eq!(
+            contract.verify_mpt_proof(
+                header.receipts_root,
+                receipt_key.clone(),
+                log_entry_proof.proof.clone()
+            ),
+            Some(log_entry_proof.receipt_data)
+        );
+        assert_eq!(
+            contract.verify_mpt_proof(
+                header.transactions_root,
+                receipt_key,
+                log_entry_proof.proof.clone()
+            ),
+            None
+        );
+        // The block has a single receipt, so its leaf proves the absence of the next one
+        assert_eq!(
+            contract.verify_mpt_proof(
+                header.receipts_root,
+                rlp::encode(&(log_entry_proof.receipt_index + 1)).to_vec(),
+                log_entry_proof.proof
+            ),
+            Some(vec![])
+        );
+    }
+
     #[test]
     fn complex_tx_res() {
         testing_env!(get_context(vec![]));
@@ -467,15 +1001,15 @@ mod tests {
 
         let expected_root = hex::decode(expected_root).unwrap().into();
         let key = hex::decode(key).unwrap();
-        let proof = proof_rlp
+        let proof: Vec<Vec<u8>> = proof_rlp
             .into_iter()
             .map(|x| hex::decode(x).unwrap())
             .collect();
         let expected_value = hex::decode(expected_value).unwrap();
 
         assert_eq!(
-            EthProver::verify_trie_proof(expected_root, key, proof),
-            expected_value
+            eth_mpt_proof::verify_proof(expected_root, &key, &proof),
+            Ok(Some(expected_value))
         );
     }
 }
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests_abi {
+    use crate::abi::{AbiError, AbiType, AbiValue, EventParam, EventSignature};
+    use eth_types::{near_keccak256, H256, U256};
+
+    fn word(value: u64) -> Vec<u8> {
+        let mut word = vec![0u8; 24];
+        word.extend_from_slice(&value.to_be_bytes());
+        word
+    }
+
+    #[test]
+    fn test_parse_event_signature() {
+        let signature = EventSignature::parse(
+            " Deposit(address indexed sender, uint amount, string  indexed memo, bytes32)",
+        )
+        .unwrap();
+        assert_eq!(signature.name, "Deposit");
+        assert_eq!(
+            signature.params[1],
+            EventParam {
+                abi_type: AbiType::Uint(256),
+                indexed: false
+            }
+        );
+        assert!(signature.params[2].indexed);
+        assert_eq!(
+            signature.canonical(),
+            "Deposit(address,uint256,string,bytes32)"
+        );
+        assert_eq!(
+            signature.topic(),
+            H256(near_keccak256(b"Deposit(address,uint256,string,bytes32)").into())
+        );
+
+        for signature in [
+            "Deposit",
+            "Deposit(uint7)",
+            "Deposit(bytes33)",
+            "Deposit(uint256[])",
+            "Deposit((uint256,bool))",
+            "Deposit(uint256 indexed amount extra)",
+        ] {
+            assert_eq!(
+                EventSignature::parse(signature),
+                Err(AbiError::InvalidSignature)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_dynamic_and_signed_params() {
+        let signature = EventSignature::parse("Memo(int64 delta, string memo, bool ok)").unwrap();
+        let mut data = vec![0xffu8; 24];
+        data.extend_from_slice(&(-5i64).to_be_bytes());
+        // The offset of the string in the tail
+        data.extend(word(96));
+        data.extend(word(1));
+        data.extend(word(5));
+        let mut memo = b"hello".to_vec();
+        memo.resize(32, 0);
+        data.extend(memo);
+
+        let mut delta = [0xffu8; 32];
+        delta[31] = 0xfb;
+        assert_eq!(
+            signature.decode_log(&[signature.topic()], &data),
+            Ok(vec![
+                AbiValue::Int(U256(delta[..].into())),
+                AbiValue::String("hello".to_string()),
+                AbiValue::Bool(true),
+            ])
+        );
+
+        // The string is out of the data
+        assert_eq!(
+            signature.decode_log(&[signature.topic()], &data[..130]),
+            Err(AbiError::InvalidData)
+        );
+        // The indexed parameters are missing
+        assert_eq!(
+            signature.decode_log(&[signature.topic(), signature.topic()], &data),
+            Err(AbiError::InvalidData)
+        );
+    }
+}
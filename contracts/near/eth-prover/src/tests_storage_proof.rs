@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests_storage_proof {
-    use crate::EthProver;
-    use eth_types::H256;
+    use crate::events::{EthProverEvent, ProofKind};
+    use crate::{EthProver, FeeSchedule, StorageSlotProof, MAX_STORAGE_SLOTS_PER_BATCH};
+    use eth_types::events::NearEvent;
+    use eth_types::{near_keccak256, AccountState, BlockHeader, BlockHeaderRoots, H256};
+    use near_sdk::json_types::U128;
     use near_sdk::serde_json;
     use near_sdk::PromiseOrValue;
     use rlp::Rlp;
@@ -80,7 +83,11 @@ mod tests_storage_proof {
 
     // TESTS
 
-    use near_sdk::{testing_env, VMContext};
+    use crate::test_utils::get_single_key_trie;
+    use near_sdk::borsh::BorshSerialize;
+    use near_sdk::mock::VmAction;
+    use near_sdk::test_utils::{get_created_receipts, get_logs};
+    use near_sdk::{testing_env, PromiseResult, RuntimeFeesConfig, VMConfig, VMContext};
 
     fn get_context(input: Vec<u8>) -> VMContext {
         VMContext {
@@ -106,19 +113,19 @@ mod tests_storage_proof {
     }
 
     #[test]
-    pub fn test_verify_trie_proof() {
+    pub fn test_verify_mpt_proof() {
         testing_env!(get_context(vec![]));
         let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
         let storage_hash: H256 = Rlp::new(&test_data.expected_account_state)
             .val_at(2)
             .unwrap();
         assert_eq!(
-            EthProver::verify_trie_proof(
+            eth_mpt_proof::verify_proof(
                 storage_hash,
-                test_data.storage_key_hash,
-                test_data.storage_proof
+                &test_data.storage_key_hash,
+                &test_data.storage_proof
             ),
-            test_data.expected_storage_value
+            Ok(Some(test_data.expected_storage_value))
         );
     }
 
@@ -144,14 +151,115 @@ mod tests_storage_proof {
         }
     }
 
+    fn get_verify_storage_proof_args(test_data: StorageProof) -> Vec<u8> {
+        (
+            test_data.header_data,
+            test_data.account_proof,
+            test_data.contract_address,
+            test_data.expected_account_state,
+            test_data.storage_key_hash,
+            test_data.storage_proof,
+            test_data.expected_storage_value,
+            test_data.min_header_height,
+            test_data.max_header_height,
+            test_data.skip_bridge_call,
+        )
+            .try_to_vec()
+            .unwrap()
+    }
+
+    fn set_fee_schedule(contract: &mut EthProver, storage_proof_fee: u128) {
+        contract.update_fee_schedule(FeeSchedule {
+            log_entry_fee: U128(0),
+            storage_proof_fee: U128(storage_proof_fee),
+            transaction_fee: U128(0),
+            account_proof_fee: U128(0),
+            withdrawal_fee: U128(0),
+        });
+    }
+
     #[test]
-    #[should_panic(expected = "assertion failed: `(left == right)")]
-    pub fn test_verify_storage_proof_with_wrong_account_proof() {
+    pub fn test_verify_storage_proof_with_fee() {
         testing_env!(get_context(vec![]));
-        let contract = EthProver::init("ethbridge".to_string());
-        let test_data = get_storage_proof(String::from(
-            "./src/test_data/storageProofWithIncorrectAccountProof.json",
-        ));
+        let mut contract = EthProver::init("ethbridge".to_string());
+        set_fee_schedule(&mut contract, 100);
+
+        let mut context = get_context(vec![]);
+        context.attached_deposit = 150;
+        testing_env!(context);
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        contract.verify_with_fee(
+            "verify_storage_proof".to_string(),
+            get_verify_storage_proof_args(test_data),
+        );
+        assert_eq!(contract.get_accumulated_fees().0, 100);
+
+        let receipts = get_created_receipts();
+        assert!(receipts.iter().any(|receipt| {
+            receipt.receiver_id.as_str() == "alice.near"
+                && receipt.actions.iter().any(|action| {
+                    matches!(
+                        action,
+                        VmAction::FunctionCall { function_name, .. }
+                            if function_name == "verify_storage_proof"
+                    )
+                })
+        }));
+        assert!(receipts.iter().any(|receipt| {
+            receipt.receiver_id.as_str() == "carol.near"
+                && receipt
+                    .actions
+                    .iter()
+                    .any(|action| matches!(action, VmAction::Transfer { deposit: 50 }))
+        }));
+    }
+
+    #[test]
+    #[should_panic(expected = "The attached deposit should cover the verification fee")]
+    pub fn test_verify_storage_proof_without_fee() {
+        testing_env!(get_context(vec![]));
+        let mut contract = EthProver::init("ethbridge".to_string());
+        set_fee_schedule(&mut contract, 100);
+
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        contract.verify_with_fee(
+            "verify_storage_proof".to_string(),
+            get_verify_storage_proof_args(test_data),
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The verification fee is set, call the method through verify_with_fee"
+    )]
+    pub fn test_verify_storage_proof_with_bridge_call_requires_fee() {
+        testing_env!(get_context(vec![]));
+        let mut contract = EthProver::init("ethbridge".to_string());
+        set_fee_schedule(&mut contract, 100);
+
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        contract.verify_storage_proof(
+            test_data.header_data,
+            test_data.account_proof,
+            test_data.contract_address,
+            test_data.expected_account_state,
+            test_data.storage_key_hash,
+            test_data.storage_proof,
+            test_data.expected_storage_value,
+            test_data.min_header_height,
+            test_data.max_header_height,
+            false,
+        );
+    }
+
+    #[test]
+    pub fn test_verify_storage_proof_without_bridge_call_is_free() {
+        testing_env!(get_context(vec![]));
+        let mut contract = EthProver::init("ethbridge".to_string());
+        set_fee_schedule(&mut contract, 100);
+
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        assert!(test_data.skip_bridge_call);
         if let PromiseOrValue::Value(true) = contract.verify_storage_proof(
             test_data.header_data,
             test_data.account_proof,
@@ -167,17 +275,68 @@ mod tests_storage_proof {
         } else {
             panic!();
         }
+        assert_eq!(contract.get_accumulated_fees().0, 0);
+    }
+
+    #[test]
+    pub fn test_failed_fees_withdrawal_is_restored() {
+        let mut context = get_context(vec![]);
+        context.predecessor_account_id = context.current_account_id.clone();
+        testing_env!(context.clone());
+        let mut contract = EthProver::init("ethbridge".to_string());
+
+        testing_env!(
+            context.clone(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])]
+        );
+        contract.on_fees_withdrawn(U128(100));
+        assert_eq!(contract.get_accumulated_fees().0, 0);
+
+        testing_env!(
+            context,
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+        contract.on_fees_withdrawn(U128(100));
+        assert_eq!(contract.get_accumulated_fees().0, 100);
     }
 
     #[test]
-    #[should_panic(expected = "assertion failed: `(left == right)")]
+    #[should_panic(expected = "Invalid account proof")]
+    pub fn test_verify_storage_proof_with_wrong_account_proof() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let test_data = get_storage_proof(String::from(
+            "./src/test_data/storageProofWithIncorrectAccountProof.json",
+        ));
+        contract.verify_storage_proof(
+            test_data.header_data,
+            test_data.account_proof,
+            test_data.contract_address,
+            test_data.expected_account_state,
+            test_data.storage_key_hash,
+            test_data.storage_proof,
+            test_data.expected_storage_value,
+            test_data.min_header_height,
+            test_data.max_header_height,
+            test_data.skip_bridge_call,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid storage proof")]
     pub fn test_verify_storage_proof_with_wrong_state_proof() {
         testing_env!(get_context(vec![]));
         let contract = EthProver::init("ethbridge".to_string());
         let test_data = get_storage_proof(String::from(
             "./src/test_data/storageProofWithIncorrectStateProof.json",
         ));
-        if let PromiseOrValue::Value(true) = contract.verify_storage_proof(
+        contract.verify_storage_proof(
             test_data.header_data,
             test_data.account_proof,
             test_data.contract_address,
@@ -188,10 +347,7 @@ mod tests_storage_proof {
             test_data.min_header_height,
             test_data.max_header_height,
             test_data.skip_bridge_call,
-        ) {
-        } else {
-            panic!();
-        }
+        );
     }
 
     #[test]
@@ -293,4 +449,187 @@ mod tests_storage_proof {
             panic!();
         }
     }
+
+    #[test]
+    pub fn test_verify_storage_proofs() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        let storage_slot_proofs = vec![
+            StorageSlotProof {
+                storage_key_hash: test_data.storage_key_hash.clone(),
+                storage_proof: test_data.storage_proof.clone(),
+                expected_storage_value: test_data.expected_storage_value.clone(),
+            },
+            StorageSlotProof {
+                storage_key_hash: test_data.storage_key_hash,
+                storage_proof: test_data.storage_proof,
+                expected_storage_value: vec![],
+            },
+        ];
+
+        if let PromiseOrValue::Value(slot_results) = contract.verify_storage_proofs(
+            test_data.header_data,
+            test_data.account_proof,
+            test_data.contract_address,
+            test_data.expected_account_state,
+            storage_slot_proofs,
+            true,
+        ) {
+            assert_eq!(slot_results, vec![true, false]);
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "The list of storage slots is too long")]
+    pub fn test_verify_storage_proofs_with_too_many_slots() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        let storage_slot_proofs = (0..=MAX_STORAGE_SLOTS_PER_BATCH)
+            .map(|_| StorageSlotProof {
+                storage_key_hash: test_data.storage_key_hash.clone(),
+                storage_proof: test_data.storage_proof.clone(),
+                expected_storage_value: test_data.expected_storage_value.clone(),
+            })
+            .collect();
+        contract.verify_storage_proofs(
+            test_data.header_data,
+            test_data.account_proof,
+            test_data.contract_address,
+            test_data.expected_account_state,
+            storage_slot_proofs,
+            true,
+        );
+    }
+
+    #[test]
+    pub fn test_verify_account() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        let account_state = match contract.verify_account(
+            test_data.header_data,
+            test_data.account_proof,
+            test_data.contract_address,
+            true,
+        ) {
+            PromiseOrValue::Value(Some(account_state)) => account_state,
+            _ => panic!(),
+        };
+
+        let expected_account_state = Rlp::new(&test_data.expected_account_state);
+        assert_eq!(
+            account_state,
+            AccountState {
+                nonce: expected_account_state.val_at(0).unwrap(),
+                balance: expected_account_state.val_at(1).unwrap(),
+                storage_root: expected_account_state.val_at(2).unwrap(),
+                code_hash: expected_account_state.val_at(3).unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_verify_account_absent() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+
+        // The state trie contains only the account of the contract, so the proof of its leaf
+        // proves that any other account doesn't exist
+        let (state_root, account_proof) = get_single_key_trie(
+            &near_keccak256(&test_data.contract_address),
+            &test_data.expected_account_state,
+        );
+        let mut header: BlockHeader = rlp::decode(test_data.header_data.as_slice()).unwrap();
+        header.state_root = state_root;
+
+        let account_state = contract.verify_account(
+            rlp::encode(&header).to_vec(),
+            account_proof,
+            vec![1; 20],
+            true,
+        );
+        assert!(matches!(
+            account_state,
+            PromiseOrValue::Value(Some(account_state)) if account_state == AccountState::empty()
+        ));
+    }
+
+    #[test]
+    pub fn test_verify_account_with_wrong_account_proof() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let test_data = get_storage_proof(String::from(
+            "./src/test_data/storageProofWithIncorrectAccountProof.json",
+        ));
+        let account_state = contract.verify_account(
+            test_data.header_data,
+            test_data.account_proof,
+            test_data.contract_address,
+            true,
+        );
+        assert!(matches!(account_state, PromiseOrValue::Value(None)));
+    }
+
+    #[test]
+    pub fn test_verify_account_with_wrong_address_length() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        let account_state = contract.verify_account(
+            test_data.header_data,
+            test_data.account_proof,
+            vec![1; 32],
+            true,
+        );
+        assert!(matches!(account_state, PromiseOrValue::Value(None)));
+    }
+
+    #[test]
+    pub fn test_verify_storage_proof_with_header_roots() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        let header: BlockHeader = rlp::decode(test_data.header_data.as_slice()).unwrap();
+        assert!(contract.on_header_roots_for_storage_proof(
+            Some(BlockHeaderRoots::from(&header)),
+            header.number,
+            test_data.account_proof,
+            test_data.contract_address,
+            test_data.expected_account_state,
+            test_data.storage_key_hash,
+            test_data.storage_proof,
+            test_data.expected_storage_value,
+        ));
+
+        let expected_event = EthProverEvent::ProofVerified {
+            proof_kind: ProofKind::StorageProof,
+            block_number: header.number,
+        };
+        assert_eq!(
+            get_logs(),
+            vec![format!("EVENT_JSON:{}", expected_event.to_json_string())]
+        );
+    }
+
+    #[test]
+    pub fn test_verify_storage_proof_without_header_roots() {
+        testing_env!(get_context(vec![]));
+        let contract = EthProver::init("ethbridge".to_string());
+        let test_data = get_storage_proof(String::from("./src/test_data/storageProof.json"));
+        assert!(!contract.on_header_roots_for_storage_proof(
+            None,
+            0,
+            test_data.account_proof,
+            test_data.contract_address,
+            test_data.expected_account_state,
+            test_data.storage_key_hash,
+            test_data.storage_proof,
+            test_data.expected_storage_value,
+        ));
+    }
 }
@@ -0,0 +1,369 @@
+#[cfg(test)]
+mod tests_transaction {
+    use crate::test_utils::{get_context, get_single_leaf_trie};
+    use crate::transaction::{
+        decode_transaction, AccessListItem, Authorization, ACCESS_LIST_TX_TYPE, BLOB_TX_TYPE,
+        DYNAMIC_FEE_TX_TYPE, LEGACY_TX_TYPE, SET_CODE_TX_TYPE,
+    };
+    use crate::EthProver;
+    use eth_types::{near_keccak256, Address, H160, H256, U256};
+    use near_crypto::{KeyType, SecretKey, Signature};
+    use near_sdk::testing_env;
+    use rlp::RlpStream;
+
+    fn get_secret_key() -> SecretKey {
+        SecretKey::from_seed(KeyType::SECP256K1, "eth-sender")
+    }
+
+    fn get_sender_address(secret_key: &SecretKey) -> Address {
+        let public_key = secret_key.public_key();
+        let address: [u8; 20] = near_keccak256(public_key.key_data())[12..]
+            .try_into()
+            .unwrap();
+        H160(address.into())
+    }
+
+    fn sign(secret_key: &SecretKey, data: &[u8]) -> (u8, U256, U256) {
+        let signature: [u8; 65] = match secret_key.sign(&near_keccak256(data)) {
+            Signature::SECP256K1(signature) => signature.into(),
+            _ => unreachable!(),
+        };
+        (
+            signature[64],
+            U256(signature[..32].into()),
+            U256(signature[32..64].into()),
+        )
+    }
+
+    fn get_recipient() -> Address {
+        H160([0x11u8; 20].into())
+    }
+
+    /// Appends the fields of the EIP-1559 transaction preceding the signature
+    fn append_dynamic_fee_tx_fields(stream: &mut RlpStream) {
+        stream.append(&1u64);
+        stream.append(&7u64);
+        stream.append(&U256(2_000_000_000u64.into()));
+        stream.append(&U256(30_000_000_000u64.into()));
+        stream.append(&21_000u64);
+        stream.append(&get_recipient());
+        stream.append(&U256(1_000_000u64.into()));
+        stream.append(&vec![0xde, 0xad]);
+        stream.begin_list(1);
+        stream.begin_list(2);
+        stream.append(&get_recipient());
+        stream.begin_list(1);
+        stream.append(&H256([0x22u8; 32].into()));
+    }
+
+    /// Appends the fields of the EIP-2930 transaction preceding the signature
+    fn append_access_list_tx_fields(stream: &mut RlpStream) {
+        stream.append(&1u64);
+        stream.append(&8u64);
+        stream.append(&U256(10_000_000_000u64.into()));
+        stream.append(&50_000u64);
+        stream.append(&get_recipient());
+        stream.append(&U256(0u64.into()));
+        stream.append(&vec![0xbe, 0xef]);
+        stream.begin_list(1);
+        stream.begin_list(2);
+        stream.append(&get_recipient());
+        stream.begin_list(0);
+    }
+
+    /// Appends the fields of the EIP-4844 transaction preceding the signature
+    fn append_blob_tx_fields(stream: &mut RlpStream) {
+        append_dynamic_fee_tx_fields(stream);
+        stream.append(&U256(3_000_000_000u64.into()));
+        stream.begin_list(2);
+        stream.append(&H256([0x01u8; 32].into()));
+        stream.append(&H256([0x02u8; 32].into()));
+    }
+
+    /// Appends the fields of the EIP-7702 transaction preceding the signature
+    fn append_set_code_tx_fields(stream: &mut RlpStream) {
+        append_dynamic_fee_tx_fields(stream);
+        stream.begin_list(1);
+        stream.begin_list(6);
+        stream.append(&U256(1u64.into()));
+        stream.append(&H160([0x33u8; 20].into()));
+        stream.append(&9u64);
+        stream.append(&1u8);
+        stream.append(&U256(0x44u64.into()));
+        stream.append(&U256(0x55u64.into()));
+    }
+
+    /// Signs the EIP-2718 transaction with `fields_count` fields preceding the signature
+    fn get_typed_tx(
+        secret_key: &SecretKey,
+        tx_type: u8,
+        fields_count: usize,
+        append_fields: fn(&mut RlpStream),
+    ) -> Vec<u8> {
+        let mut unsigned_tx = RlpStream::new_list(fields_count);
+        append_fields(&mut unsigned_tx);
+        let mut signed_data = vec![tx_type];
+        signed_data.extend_from_slice(&unsigned_tx.out());
+        let (y_parity, r, s) = sign(secret_key, &signed_data);
+
+        let mut tx = RlpStream::new_list(fields_count + 3);
+        append_fields(&mut tx);
+        tx.append(&y_parity);
+        tx.append(&r);
+        tx.append(&s);
+
+        let mut tx_data = vec![tx_type];
+        tx_data.extend_from_slice(&tx.out());
+        tx_data
+    }
+
+    fn get_dynamic_fee_tx(secret_key: &SecretKey) -> Vec<u8> {
+        get_typed_tx(
+            secret_key,
+            DYNAMIC_FEE_TX_TYPE,
+            9,
+            append_dynamic_fee_tx_fields,
+        )
+    }
+
+    /// Appends the fields of the legacy contract creation transaction preceding the signature
+    fn append_legacy_tx_fields(stream: &mut RlpStream) {
+        stream.append(&3u64);
+        stream.append(&U256(10_000_000_000u64.into()));
+        stream.append(&100_000u64);
+        stream.append_empty_data();
+        stream.append(&U256(0u64.into()));
+        stream.append(&vec![0x60, 0x80]);
+    }
+
+    fn get_legacy_tx(secret_key: &SecretKey, chain_id: u64) -> Vec<u8> {
+        let mut unsigned_tx = RlpStream::new_list(9);
+        append_legacy_tx_fields(&mut unsigned_tx);
+        unsigned_tx.append(&chain_id);
+        unsigned_tx.append(&0u8);
+        unsigned_tx.append(&0u8);
+        let (recovery_id, r, s) = sign(secret_key, &unsigned_tx.out());
+
+        let mut tx = RlpStream::new_list(9);
+        append_legacy_tx_fields(&mut tx);
+        tx.append(&(chain_id * 2 + 35 + recovery_id as u64));
+        tx.append(&r);
+        tx.append(&s);
+        tx.out().to_vec()
+    }
+
+    #[test]
+    pub fn test_decode_dynamic_fee_transaction() {
+        testing_env!(get_context(vec![]));
+        let secret_key = get_secret_key();
+        let tx_data = get_dynamic_fee_tx(&secret_key);
+
+        let transaction = decode_transaction(&tx_data).unwrap();
+        assert_eq!(transaction.tx_type, DYNAMIC_FEE_TX_TYPE);
+        assert_eq!(transaction.hash, H256(near_keccak256(&tx_data).into()));
+        assert_eq!(transaction.chain_id, Some(1));
+        assert_eq!(transaction.nonce, 7);
+        assert_eq!(transaction.gas_limit, 21_000);
+        assert_eq!(transaction.gas_price, None);
+        assert_eq!(
+            transaction.max_fee_per_gas,
+            Some(U256(30_000_000_000u64.into()))
+        );
+        assert_eq!(transaction.from, get_sender_address(&secret_key));
+        assert_eq!(transaction.to, Some(get_recipient()));
+        assert_eq!(transaction.value, U256(1_000_000u64.into()));
+        assert_eq!(transaction.input, vec![0xde, 0xad]);
+        assert_eq!(
+            transaction.access_list,
+            vec![AccessListItem {
+                address: get_recipient(),
+                storage_keys: vec![H256([0x22u8; 32].into())],
+            }]
+        );
+    }
+
+    #[test]
+    pub fn test_decode_legacy_transaction() {
+        testing_env!(get_context(vec![]));
+        let secret_key = get_secret_key();
+        let tx_data = get_legacy_tx(&secret_key, 5);
+
+        let transaction = decode_transaction(&tx_data).unwrap();
+        assert_eq!(transaction.tx_type, LEGACY_TX_TYPE);
+        assert_eq!(transaction.chain_id, Some(5));
+        assert_eq!(transaction.nonce, 3);
+        assert_eq!(transaction.gas_price, Some(U256(10_000_000_000u64.into())));
+        assert_eq!(transaction.from, get_sender_address(&secret_key));
+        assert_eq!(transaction.to, None);
+        assert_eq!(transaction.input, vec![0x60, 0x80]);
+    }
+
+    #[test]
+    pub fn test_verify_transaction_inclusion() {
+        testing_env!(get_context(vec![]));
+        let secret_key = get_secret_key();
+        let tx_data = get_dynamic_fee_tx(&secret_key);
+        let (transactions_root, proof) = get_single_leaf_trie(0, &tx_data);
+
+        let transaction =
+            EthProver::verify_transaction_inclusion(transactions_root, 0, &tx_data, proof).unwrap();
+        assert_eq!(transaction.from, get_sender_address(&secret_key));
+    }
+
+    #[test]
+    pub fn test_verify_transaction_inclusion_with_wrong_data() {
+        testing_env!(get_context(vec![]));
+        let secret_key = get_secret_key();
+        let tx_data = get_dynamic_fee_tx(&secret_key);
+        let (transactions_root, proof) = get_single_leaf_trie(0, &tx_data);
+
+        let other_tx_data = get_legacy_tx(&secret_key, 1);
+        assert!(EthProver::verify_transaction_inclusion(
+            transactions_root,
+            0,
+            &other_tx_data,
+            proof
+        )
+        .is_none());
+    }
+
+    #[test]
+    pub fn test_decode_access_list_transaction() {
+        testing_env!(get_context(vec![]));
+        let secret_key = get_secret_key();
+        let tx_data = get_typed_tx(
+            &secret_key,
+            ACCESS_LIST_TX_TYPE,
+            8,
+            append_access_list_tx_fields,
+        );
+
+        let transaction = decode_transaction(&tx_data).unwrap();
+        assert_eq!(transaction.tx_type, ACCESS_LIST_TX_TYPE);
+        assert_eq!(transaction.hash, H256(near_keccak256(&tx_data).into()));
+        assert_eq!(transaction.chain_id, Some(1));
+        assert_eq!(transaction.nonce, 8);
+        assert_eq!(transaction.gas_price, Some(U256(10_000_000_000u64.into())));
+        assert_eq!(transaction.max_fee_per_gas, None);
+        assert_eq!(transaction.gas_limit, 50_000);
+        assert_eq!(transaction.from, get_sender_address(&secret_key));
+        assert_eq!(transaction.to, Some(get_recipient()));
+        assert_eq!(transaction.input, vec![0xbe, 0xef]);
+        assert_eq!(
+            transaction.access_list,
+            vec![AccessListItem {
+                address: get_recipient(),
+                storage_keys: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    pub fn test_decode_blob_transaction() {
+        testing_env!(get_context(vec![]));
+        let secret_key = get_secret_key();
+        let tx_data = get_typed_tx(&secret_key, BLOB_TX_TYPE, 11, append_blob_tx_fields);
+
+        let transaction = decode_transaction(&tx_data).unwrap();
+        assert_eq!(transaction.tx_type, BLOB_TX_TYPE);
+        assert_eq!(transaction.hash, H256(near_keccak256(&tx_data).into()));
+        assert_eq!(transaction.nonce, 7);
+        assert_eq!(
+            transaction.max_priority_fee_per_gas,
+            Some(U256(2_000_000_000u64.into()))
+        );
+        assert_eq!(
+            transaction.max_fee_per_blob_gas,
+            Some(U256(3_000_000_000u64.into()))
+        );
+        assert_eq!(transaction.from, get_sender_address(&secret_key));
+        assert_eq!(transaction.access_list.len(), 1);
+        assert_eq!(
+            transaction.blob_versioned_hashes,
+            vec![H256([0x01u8; 32].into()), H256([0x02u8; 32].into())]
+        );
+        assert!(transaction.authorization_list.is_empty());
+    }
+
+    #[test]
+    pub fn test_decode_set_code_transaction() {
+        testing_env!(get_context(vec![]));
+        let secret_key = get_secret_key();
+        let tx_data = get_typed_tx(&secret_key, SET_CODE_TX_TYPE, 10, append_set_code_tx_fields);
+
+        let transaction = decode_transaction(&tx_data).unwrap();
+        assert_eq!(transaction.tx_type, SET_CODE_TX_TYPE);
+        assert_eq!(transaction.hash, H256(near_keccak256(&tx_data).into()));
+        assert_eq!(transaction.nonce, 7);
+        assert_eq!(transaction.max_fee_per_blob_gas, None);
+        assert_eq!(transaction.from, get_sender_address(&secret_key));
+        assert!(transaction.blob_versioned_hashes.is_empty());
+        assert_eq!(
+            transaction.authorization_list,
+            vec![Authorization {
+                chain_id: U256(1u64.into()),
+                address: H160([0x33u8; 20].into()),
+                nonce: 9,
+                y_parity: 1,
+                r: U256(0x44u64.into()),
+                s: U256(0x55u64.into()),
+            }]
+        );
+    }
+
+    /// The legacy transaction of the Goerli block 8709522
+    /// (eth2near/eth_rpc_client/data/beacon_block_goerli_slot_5262172.json)
+    #[test]
+    pub fn test_decode_goerli_legacy_transaction() {
+        testing_env!(get_context(vec![]));
+        let tx_data = hex::decode("f8688306b8c78544a49b1431830186a094f5e8a439c599205c1ab06b535de46681aed1007a80802da042c253910a7f097e3ac6d8ff0b279680b15b9744488622e436fbf5154617e5a3a00c8d4e67a82e6525fa086e1d86aa606f460e1b3963bd94d59f9d1eb8910157b1").unwrap();
+
+        let transaction = decode_transaction(&tx_data).unwrap();
+        assert_eq!(transaction.tx_type, LEGACY_TX_TYPE);
+        assert_eq!(
+            transaction.hash,
+            H256::from(
+                hex::decode("2107a62a586658db9e507bae554a23e92075b6d2227d5bf548a31bddf3f9f44b")
+                    .unwrap()
+            )
+        );
+        assert_eq!(transaction.chain_id, Some(5));
+        assert_eq!(transaction.nonce, 440_519);
+        assert_eq!(transaction.gas_limit, 100_000);
+        assert_eq!(
+            transaction.from,
+            H160::from(hex::decode("f5e8a439c599205c1ab06b535de46681aed1007a").unwrap())
+        );
+    }
+
+    /// The EIP-1559 transaction of the Goerli block 8709522
+    /// (eth2near/eth_rpc_client/data/beacon_block_goerli_slot_5262172.json)
+    #[test]
+    pub fn test_decode_goerli_dynamic_fee_transaction() {
+        testing_env!(get_context(vec![]));
+        let tx_data = hex::decode("02f87205118459682f00852b5307858482520894d3c928bfa568ec5b9c7d9904ed5af9b8e45de2c787470de4df82000080c001a037cc4ac643b78ba281242e1bf33260d2f5e323de881d34164b6cdde2e82d227aa070a9413e377a69526411063cc68ae1849e89575497ab09f6d35b194fe9486d77").unwrap();
+
+        let transaction = decode_transaction(&tx_data).unwrap();
+        assert_eq!(transaction.tx_type, DYNAMIC_FEE_TX_TYPE);
+        assert_eq!(
+            transaction.hash,
+            H256::from(
+                hex::decode("52fc03c80b368bf62954d0c19f2cc7829da512e61bfd705c9466e50832c55033")
+                    .unwrap()
+            )
+        );
+        assert_eq!(transaction.chain_id, Some(5));
+        assert_eq!(transaction.nonce, 17);
+        assert_eq!(transaction.value, U256(20_000_000_000_000_000u64.into()));
+        assert_eq!(
+            transaction.from,
+            H160::from(hex::decode("f0fd6ec230ce22e9ad142c401a1ab21ff89b679d").unwrap())
+        );
+        assert_eq!(
+            transaction.to,
+            Some(H160::from(
+                hex::decode("d3c928bfa568ec5b9c7d9904ed5af9b8e45de2c7").unwrap()
+            ))
+        );
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests_withdrawal {
+    use crate::test_utils::{get_context, get_single_leaf_trie};
+    use crate::EthProver;
+    use eth_types::{Withdrawal, H160};
+    use near_sdk::testing_env;
+    use rlp::RlpStream;
+
+    fn get_withdrawal() -> Withdrawal {
+        Withdrawal {
+            index: 17_000_000,
+            validator_index: 123_456,
+            address: H160([0x33u8; 20].into()),
+            amount: 16_000_000,
+        }
+    }
+
+    fn encode_withdrawal(withdrawal: &Withdrawal) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&withdrawal.index);
+        stream.append(&withdrawal.validator_index);
+        stream.append(&withdrawal.address);
+        stream.append(&withdrawal.amount);
+        stream.out().to_vec()
+    }
+
+    #[test]
+    pub fn test_verify_withdrawal_inclusion() {
+        testing_env!(get_context(vec![]));
+        let withdrawal_data = encode_withdrawal(&get_withdrawal());
+        let (withdrawals_root, proof) = get_single_leaf_trie(0, &withdrawal_data);
+
+        assert_eq!(
+            EthProver::verify_withdrawal_inclusion(withdrawals_root, 0, &withdrawal_data, proof),
+            Some(get_withdrawal())
+        );
+    }
+
+    #[test]
+    pub fn test_verify_withdrawal_inclusion_with_wrong_data() {
+        testing_env!(get_context(vec![]));
+        let withdrawal_data = encode_withdrawal(&get_withdrawal());
+        let (withdrawals_root, proof) = get_single_leaf_trie(0, &withdrawal_data);
+
+        let other_withdrawal_data = encode_withdrawal(&Withdrawal {
+            amount: 32_000_000_000,
+            ..get_withdrawal()
+        });
+        assert_eq!(
+            EthProver::verify_withdrawal_inclusion(
+                withdrawals_root,
+                0,
+                &other_withdrawal_data,
+                proof
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use eth_types::*;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::env;
+use rlp::{DecoderError, Rlp, RlpStream};
+
+/// https://eips.ethereum.org/EIPS/eip-2930
+pub const ACCESS_LIST_TX_TYPE: u8 = 0x01;
+/// https://eips.ethereum.org/EIPS/eip-1559
+pub const DYNAMIC_FEE_TX_TYPE: u8 = 0x02;
+/// https://eips.ethereum.org/EIPS/eip-4844
+pub const BLOB_TX_TYPE: u8 = 0x03;
+/// https://eips.ethereum.org/EIPS/eip-7702
+pub const SET_CODE_TX_TYPE: u8 = 0x04;
+/// The pseudo type of the pre EIP-2718 transactions
+pub const LEGACY_TX_TYPE: u8 = 0x00;
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub struct AccessListItem {
+    pub address: Address,
+    pub storage_keys: Vec<H256>,
+}
+
+impl rlp::Decodable for AccessListItem {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        Ok(Self {
+            address: rlp.val_at(0)?,
+            storage_keys: rlp.list_at(1)?,
+        })
+    }
+}
+
+/// The signed authorization of the EIP-7702 transaction
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub struct Authorization {
+    pub chain_id: U256,
+    pub address: Address,
+    pub nonce: u64,
+    pub y_parity: u8,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl rlp::Decodable for Authorization {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            address: rlp.val_at(1)?,
+            nonce: rlp.val_at(2)?,
+            y_parity: rlp.val_at(3)?,
+            r: rlp.val_at(4)?,
+            s: rlp.val_at(5)?,
+        })
+    }
+}
+
+/// The transaction decoded from the transactions trie. The fields which are not defined
+/// for the transaction type are set to `None` or left empty.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub struct Transaction {
+    /// EIP-2718 type, `LEGACY_TX_TYPE` for the legacy transactions
+    pub tx_type: u8,
+    /// Keccak256 of the transaction data
+    pub hash: H256,
+    /// Not set for the legacy transactions signed without EIP-155 replay protection
+    pub chain_id: Option<u64>,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub gas_price: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub max_fee_per_blob_gas: Option<U256>,
+    /// The sender recovered from the signature
+    pub from: Address,
+    /// `None` for the contract creation
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+    pub blob_versioned_hashes: Vec<H256>,
+    pub authorization_list: Vec<Authorization>,
+}
+
+/// The position of the transaction fields in the RLP list of each transaction type
+struct TransactionLayout {
+    nonce: usize,
+    gas_price: Option<usize>,
+    max_priority_fee_per_gas: Option<usize>,
+    max_fee_per_gas: Option<usize>,
+    gas_limit: usize,
+    to: usize,
+    value: usize,
+    input: usize,
+    access_list: Option<usize>,
+    max_fee_per_blob_gas: Option<usize>,
+    blob_versioned_hashes: Option<usize>,
+    authorization_list: Option<usize>,
+    /// The number of the fields including `v`/`y_parity`, `r` and `s`
+    fields_count: usize,
+}
+
+impl TransactionLayout {
+    fn for_type(tx_type: u8) -> Result<Self, DecoderError> {
+        let fee_market_layout = Self {
+            nonce: 1,
+            gas_price: None,
+            max_priority_fee_per_gas: Some(2),
+            max_fee_per_gas: Some(3),
+            gas_limit: 4,
+            to: 5,
+            value: 6,
+            input: 7,
+            access_list: Some(8),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
+            fields_count: 12,
+        };
+
+        match tx_type {
+            LEGACY_TX_TYPE => Ok(Self {
+                nonce: 0,
+                gas_price: Some(1),
+                max_priority_fee_per_gas: None,
+                max_fee_per_gas: None,
+                gas_limit: 2,
+                to: 3,
+                value: 4,
+                input: 5,
+                access_list: None,
+                fields_count: 9,
+                ..fee_market_layout
+            }),
+            ACCESS_LIST_TX_TYPE => Ok(Self {
+                gas_price: Some(2),
+                max_priority_fee_per_gas: None,
+                max_fee_per_gas: None,
+                gas_limit: 3,
+                to: 4,
+                value: 5,
+                input: 6,
+                access_list: Some(7),
+                fields_count: 11,
+                ..fee_market_layout
+            }),
+            DYNAMIC_FEE_TX_TYPE => Ok(fee_market_layout),
+            BLOB_TX_TYPE => Ok(Self {
+                max_fee_per_blob_gas: Some(9),
+                blob_versioned_hashes: Some(10),
+                fields_count: 14,
+                ..fee_market_layout
+            }),
+            SET_CODE_TX_TYPE => Ok(Self {
+                authorization_list: Some(9),
+                fields_count: 13,
+                ..fee_market_layout
+            }),
+            _ => Err(DecoderError::Custom("Unsupported transaction type")),
+        }
+    }
+}
+
+fn optional_val_at<T: rlp::Decodable>(
+    rlp: &Rlp,
+    index: Option<usize>,
+) -> Result<Option<T>, DecoderError> {
+    index.map(|index| rlp.val_at(index)).transpose()
+}
+
+fn optional_list_at<T: rlp::Decodable>(
+    rlp: &Rlp,
+    index: Option<usize>,
+) -> Result<Vec<T>, DecoderError> {
+    index.map_or(Ok(vec![]), |index| rlp.list_at(index))
+}
+
+/// Decodes the transaction in the EIP-2718 envelope or the legacy transaction
+/// and recovers its sender with `env::ecrecover`.
+pub fn decode_transaction(tx_data: &[u8]) -> Result<Transaction, DecoderError> {
+    let (tx_type, payload) = match tx_data.first() {
+        // https://eips.ethereum.org/EIPS/eip-2718#transactiontype-only-goes-up-to-0x7f
+        Some(&byte) if byte <= 0x7f => (byte, &tx_data[1..]),
+        Some(_) => (LEGACY_TX_TYPE, tx_data),
+        None => return Err(DecoderError::RlpIsTooShort),
+    };
+
+    let layout = TransactionLayout::for_type(tx_type)?;
+    let rlp = Rlp::new(payload);
+    if rlp.item_count()? != layout.fields_count {
+        return Err(DecoderError::RlpIncorrectListLen);
+    }
+
+    let signature_index = layout.fields_count - 3;
+    let v: u64 = rlp.val_at(signature_index)?;
+    let r: U256 = rlp.val_at(signature_index + 1)?;
+    let s: U256 = rlp.val_at(signature_index + 2)?;
+
+    // The signed payload is the RLP list of the fields preceding the signature
+    let mut unsigned_tx = RlpStream::new();
+    let (chain_id, recovery_id) = if tx_type == LEGACY_TX_TYPE {
+        match v {
+            27 | 28 => {
+                unsigned_tx.begin_list(signature_index);
+                (None, v - 27)
+            }
+            // https://eips.ethereum.org/EIPS/eip-155
+            v if v >= 35 => {
+                let chain_id = (v - 35) / 2;
+                unsigned_tx.begin_list(signature_index + 3);
+                (Some(chain_id), v - 35 - chain_id * 2)
+            }
+            _ => return Err(DecoderError::Custom("Invalid signature v")),
+        }
+    } else {
+        unsigned_tx.begin_list(signature_index);
+        (Some(rlp.val_at(0)?), v)
+    };
+    for field in rlp.iter().take(signature_index) {
+        unsigned_tx.append_raw(field.as_raw(), 1);
+    }
+    if let (LEGACY_TX_TYPE, Some(chain_id)) = (tx_type, chain_id) {
+        unsigned_tx.append(&chain_id);
+        unsigned_tx.append(&0u8);
+        unsigned_tx.append(&0u8);
+    }
+
+    let mut signed_data = vec![];
+    if tx_type != LEGACY_TX_TYPE {
+        signed_data.push(tx_type);
+    }
+    signed_data.extend_from_slice(&unsigned_tx.out());
+
+    let from = recover_sender(&near_keccak256(&signed_data), recovery_id, r, s).ok_or(
+        DecoderError::Custom("Failed to recover the transaction sender"),
+    )?;
+
+    let to = rlp.at(layout.to)?;
+    Ok(Transaction {
+        tx_type,
+        hash: H256(near_keccak256(tx_data).into()),
+        chain_id,
+        nonce: rlp.val_at(layout.nonce)?,
+        gas_limit: rlp.val_at(layout.gas_limit)?,
+        gas_price: optional_val_at(&rlp, layout.gas_price)?,
+        max_priority_fee_per_gas: optional_val_at(&rlp, layout.max_priority_fee_per_gas)?,
+        max_fee_per_gas: optional_val_at(&rlp, layout.max_fee_per_gas)?,
+        max_fee_per_blob_gas: optional_val_at(&rlp, layout.max_fee_per_blob_gas)?,
+        from,
+        to: if to.is_empty() {
+            None
+        } else {
+            Some(to.as_val()?)
+        },
+        value: rlp.val_at(layout.value)?,
+        input: rlp.val_at(layout.input)?,
+        access_list: optional_list_at(&rlp, layout.access_list)?,
+        blob_versioned_hashes: optional_list_at(&rlp, layout.blob_versioned_hashes)?,
+        authorization_list: optional_list_at(&rlp, layout.authorization_list)?,
+    })
+}
+
+/// Returns the address of the account which signed `hash`
+fn recover_sender(hash: &[u8; 32], recovery_id: u64, r: U256, s: U256) -> Option<Address> {
+    let recovery_id = u8::try_from(recovery_id).ok().filter(|id| *id <= 1)?;
+    let mut signature = [0u8; 64];
+    r.0.to_big_endian(&mut signature[..32]);
+    s.0.to_big_endian(&mut signature[32..]);
+
+    let public_key = env::ecrecover(hash, &signature, recovery_id, true)?;
+    let address: [u8; 20] = near_keccak256(&public_key)[12..].try_into().unwrap();
+    Some(H160(address.into()))
+}
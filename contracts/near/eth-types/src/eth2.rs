@@ -12,6 +12,7 @@ use {
 pub const PUBLIC_KEY_BYTES_LEN: usize = 48;
 pub const SIGNATURE_BYTES_LEN: usize = 96;
 pub const SYNC_COMMITTEE_BITS_SIZE_IN_BYTES: usize = 512 / 8;
+pub const KZG_COMMITMENT_BYTES_LEN: usize = 48;
 
 pub type Slot = u64;
 pub type Epoch = u64;
@@ -24,10 +25,13 @@ pub struct PublicKeyBytes(pub [u8; PUBLIC_KEY_BYTES_LEN]);
 pub struct SignatureBytes(pub [u8; SIGNATURE_BYTES_LEN]);
 #[derive(Debug, Clone)]
 pub struct SyncCommitteeBits(pub [u8; SYNC_COMMITTEE_BITS_SIZE_IN_BYTES]);
+#[derive(Debug, Clone)]
+pub struct KzgCommitment(pub [u8; KZG_COMMITMENT_BYTES_LEN]);
 
 arr_wrapper_impl_tree_hash_and_borsh!(PublicKeyBytes, PUBLIC_KEY_BYTES_LEN);
 arr_wrapper_impl_tree_hash_and_borsh!(SignatureBytes, SIGNATURE_BYTES_LEN);
 arr_wrapper_impl_tree_hash_and_borsh!(SyncCommitteeBits, SYNC_COMMITTEE_BITS_SIZE_IN_BYTES);
+arr_wrapper_impl_tree_hash_and_borsh!(KzgCommitment, KZG_COMMITMENT_BYTES_LEN);
 
 #[derive(Debug, Clone, BorshDeserialize, BorshSerialize, tree_hash_derive::TreeHash)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
@@ -41,6 +45,25 @@ pub struct BeaconBlockHeader {
     pub body_root: H256,
 }
 
+/// The validator record from the `validators` list of the beacon state
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, tree_hash_derive::TreeHash)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct Validator {
+    pub pubkey: PublicKeyBytes,
+    pub withdrawal_credentials: H256,
+    #[cfg_attr(not(target_arch = "wasm32"), serde(with = "serde_utils::quoted_u64"))]
+    pub effective_balance: u64,
+    pub slashed: bool,
+    #[cfg_attr(not(target_arch = "wasm32"), serde(with = "serde_utils::quoted_u64"))]
+    pub activation_eligibility_epoch: Epoch,
+    #[cfg_attr(not(target_arch = "wasm32"), serde(with = "serde_utils::quoted_u64"))]
+    pub activation_epoch: Epoch,
+    #[cfg_attr(not(target_arch = "wasm32"), serde(with = "serde_utils::quoted_u64"))]
+    pub exit_epoch: Epoch,
+    #[cfg_attr(not(target_arch = "wasm32"), serde(with = "serde_utils::quoted_u64"))]
+    pub withdrawable_epoch: Epoch,
+}
+
 #[derive(Debug, Clone, PartialEq, tree_hash_derive::TreeHash)]
 pub struct ForkData {
     pub current_version: ForkVersion,
@@ -113,6 +136,47 @@ pub struct FinalizedHeaderUpdate {
     pub finality_branch: Vec<H256>,
 }
 
+/// Proof that an execution block belongs to the canonical chain, anchored in the
+/// `block_roots` of a finalized beacon state.
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct BlockRootsProof {
+    /// The beacon block with the proof of its execution block hash
+    pub header_update: HeaderUpdate,
+    /// Proof of the beacon block root in `block_roots` of the beacon state
+    pub block_roots_branch: Vec<H256>,
+}
+
+/// Proof that an execution block belongs to the canonical chain, anchored in the
+/// `historical_summaries` of a finalized beacon state.
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct HistoricalBlockProof {
+    /// The beacon block with the proof of its execution block hash
+    pub header_update: HeaderUpdate,
+    /// Proof of the beacon block root in `historical_summaries[i].block_summary_root`
+    pub block_roots_branch: Vec<H256>,
+    /// Proof of `historical_summaries[i].block_summary_root` in the beacon state
+    pub historical_summary_branch: Vec<H256>,
+}
+
+/// Proof that a blob KZG commitment is included in a beacon block, anchored in the
+/// finalized beacon block or in the `block_roots` of the finalized beacon state.
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct BlobCommitmentProof {
+    /// The beacon block with the proof of its execution block hash
+    pub header_update: HeaderUpdate,
+    /// Proof of the beacon block root in `block_roots` of the beacon state.
+    /// Empty if the beacon block is the finalized one
+    pub block_roots_branch: Vec<H256>,
+    pub blob_kzg_commitment: KzgCommitment,
+    #[cfg_attr(not(target_arch = "wasm32"), serde(with = "serde_utils::quoted_u64"))]
+    pub blob_index: u64,
+    /// Proof of `blob_kzg_commitments[blob_index]` in the beacon block body
+    pub commitment_branch: Vec<H256>,
+}
+
 #[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
 pub struct LightClientUpdate {
@@ -0,0 +1,32 @@
+use near_sdk::serde::Serialize;
+use near_sdk::{env, serde_json};
+
+/// Event of a contract in the NEP-297 format:
+/// `EVENT_JSON:{"standard":"...","version":"...","event":"...","data":{...}}`.
+/// The implementors are expected to be serialized with the `event` tag and the `data` content.
+pub trait NearEvent: Serialize + Sized {
+    const STANDARD: &'static str;
+    const VERSION: &'static str;
+
+    fn to_json_string(&self) -> String {
+        serde_json::to_string(&EventLog {
+            standard: Self::STANDARD,
+            version: Self::VERSION,
+            event: self,
+        })
+        .unwrap()
+    }
+
+    fn emit(&self) {
+        env::log_str(format!("EVENT_JSON:{}", self.to_json_string()).as_str());
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a, T: Serialize> {
+    standard: &'static str,
+    version: &'static str,
+    #[serde(flatten)]
+    event: &'a T,
+}
@@ -19,6 +19,7 @@ use tree_hash::{PackedEncoding, TreeHash, TreeHashType};
 
 #[cfg(feature = "eth2")]
 pub mod eth2;
+pub mod events;
 #[macro_use]
 pub mod macros;
 
@@ -337,6 +338,25 @@ impl RlpDecodable for BlockHeader {
     }
 }
 
+/// Parts of the block header needed to verify the proofs against the block
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct BlockHeaderRoots {
+    pub receipts_root: H256,
+    pub state_root: H256,
+    pub timestamp: u64,
+}
+
+impl From<&BlockHeader> for BlockHeaderRoots {
+    fn from(header: &BlockHeader) -> Self {
+        Self {
+            receipts_root: header.receipts_root,
+            state_root: header.state_root,
+            timestamp: header.timestamp,
+        }
+    }
+}
+
 // Log
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -412,6 +432,42 @@ impl From<RlpDeriveReceipt> for Receipt {
     }
 }
 
+// Account
+
+/// The account state stored in the state trie
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, RlpDecodableDerive)]
+pub struct AccountState {
+    pub nonce: u64,
+    pub balance: U256,
+    pub storage_root: H256,
+    pub code_hash: H256,
+}
+
+impl AccountState {
+    /// The state of the account which is missing in the state trie
+    pub fn empty() -> Self {
+        Self {
+            nonce: 0,
+            balance: U256::default(),
+            storage_root: H256(near_keccak256(&rlp::NULL_RLP).into()),
+            code_hash: H256(near_keccak256(&[]).into()),
+        }
+    }
+}
+
+// Withdrawal
+
+/// The validator withdrawal included into the execution block.
+/// https://eips.ethereum.org/EIPS/eip-4895
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, RlpDecodableDerive)]
+pub struct Withdrawal {
+    pub index: u64,
+    pub validator_index: u64,
+    pub address: Address,
+    /// The amount in Gwei
+    pub amount: u64,
+}
+
 pub fn near_sha256(data: &[u8]) -> [u8; 32] {
     let mut buffer = [0u8; 32];
     buffer.copy_from_slice(near_sdk::env::sha256(data).as_slice());
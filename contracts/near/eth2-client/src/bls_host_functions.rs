@@ -0,0 +1,303 @@
+//! Verification of the sync committee signatures on top of the NEAR BLS12-381 host functions
+//! (NEP-488). Unlike the `bls` crate compiled to WASM, the curve arithmetic is executed natively
+//! by the runtime, which makes the trustless mode affordable in terms of gas.
+use eth_types::eth2::{PublicKeyBytes, SignatureBytes};
+use eth_types::H256;
+use near_sdk::env;
+
+/// The domain separation tag of the Ethereum BLS signatures (proof of possession scheme)
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const FP_BYTES_LEN: usize = 48;
+const HASH_TO_FIELD_BYTES_LEN: usize = 64;
+const REGISTER_ID: u64 = u64::MAX - 2;
+
+/// The BLS12-381 base field modulus as little-endian 64-bit limbs
+const MODULUS: [u64; 6] = [
+    0xb9feffffffffaaab,
+    0x1eabfffeb153ffff,
+    0x6730d2a0f6b0f624,
+    0x64774b84f38512bf,
+    0x4b1ba7b6434bacd7,
+    0x1a0111ea397fe69a,
+];
+
+/// The negated generator of G1 in the uncompressed form
+const NEG_G1_GENERATOR: [u8; 2 * FP_BYTES_LEN] = [
+    0x17, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c, 0x4f, 0xa9, 0xac, 0x0f,
+    0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05, 0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b, 0xac, 0x58,
+    0x6c, 0x55, 0xe8, 0x3f, 0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb, 0x22, 0xc6, 0xbb,
+    0x11, 0x4d, 0x1d, 0x68, 0x55, 0xd5, 0x45, 0xa8, 0xaa, 0x7d, 0x76, 0xc8, 0xcf, 0x2e, 0x21, 0xf2,
+    0x67, 0x81, 0x6a, 0xef, 0x1d, 0xb5, 0x07, 0xc9, 0x66, 0x55, 0xb9, 0xd5, 0xca, 0xac, 0x42, 0x36,
+    0x4e, 0x6f, 0x38, 0xba, 0x0e, 0xcb, 0x75, 0x1b, 0xad, 0x54, 0xdc, 0xd6, 0xb9, 0x39, 0xc2, 0xca,
+];
+
+#[cfg(target_arch = "wasm32")]
+mod host {
+    extern "C" {
+        pub fn bls12381_p1_sum(value_len: u64, value_ptr: u64, register_id: u64) -> u64;
+        pub fn bls12381_map_fp2_to_g2(value_len: u64, value_ptr: u64, register_id: u64) -> u64;
+        pub fn bls12381_p2_sum(value_len: u64, value_ptr: u64, register_id: u64) -> u64;
+        pub fn bls12381_p1_decompress(value_len: u64, value_ptr: u64, register_id: u64) -> u64;
+        pub fn bls12381_p2_decompress(value_len: u64, value_ptr: u64, register_id: u64) -> u64;
+        pub fn bls12381_pairing_check(value_len: u64, value_ptr: u64) -> u64;
+    }
+}
+
+/// The mocked blockchain used by the unit tests doesn't provide the BLS12-381 host functions
+#[cfg(not(target_arch = "wasm32"))]
+mod host {
+    macro_rules! unsupported_host_functions {
+        ($($name:ident($($arg:ident),*)),*) => {
+            $(pub unsafe extern "C" fn $name($(_: $arg),*) -> u64 {
+                near_sdk::env::panic_str(concat!(stringify!($name), " is supported only in wasm"))
+            })*
+        };
+    }
+
+    unsupported_host_functions!(
+        bls12381_p1_sum(u64, u64, u64),
+        bls12381_map_fp2_to_g2(u64, u64, u64),
+        bls12381_p2_sum(u64, u64, u64),
+        bls12381_p1_decompress(u64, u64, u64),
+        bls12381_p2_decompress(u64, u64, u64),
+        bls12381_pairing_check(u64, u64)
+    );
+}
+
+/// Verifies the aggregate signature of the `message` signed by all `pubkeys`
+pub fn fast_aggregate_verify(
+    pubkeys: &[PublicKeyBytes],
+    message: H256,
+    signature: &SignatureBytes,
+) -> bool {
+    if pubkeys.is_empty() {
+        return false;
+    }
+
+    let compressed_pubkeys: Vec<u8> = pubkeys.iter().flat_map(|pubkey| pubkey.0).collect();
+    let pubkeys = match call_host_function(host::bls12381_p1_decompress, &compressed_pubkeys) {
+        Some(pubkeys) => pubkeys,
+        None => return false,
+    };
+
+    // Every point in the `bls12381_p1_sum` input is prefixed by its sign, `0` means positive
+    let mut sum_input = Vec::with_capacity(pubkeys.len() + pubkeys.len() / (2 * FP_BYTES_LEN));
+    for pubkey in pubkeys.chunks(2 * FP_BYTES_LEN) {
+        sum_input.push(0);
+        sum_input.extend_from_slice(pubkey);
+    }
+    let aggregate_pubkey = match call_host_function(host::bls12381_p1_sum, &sum_input) {
+        Some(aggregate_pubkey) => aggregate_pubkey,
+        None => return false,
+    };
+
+    let signature = match call_host_function(host::bls12381_p2_decompress, &signature.0) {
+        Some(signature) => signature,
+        None => return false,
+    };
+
+    let message_point = match hash_to_g2(&message.0 .0) {
+        Some(message_point) => message_point,
+        None => return false,
+    };
+
+    // e(aggregate_pubkey, H(message)) * e(-G1, signature) == 1
+    let mut pairing_input = aggregate_pubkey;
+    pairing_input.extend(message_point);
+    pairing_input.extend_from_slice(&NEG_G1_GENERATOR);
+    pairing_input.extend(signature);
+
+    unsafe {
+        host::bls12381_pairing_check(pairing_input.len() as _, pairing_input.as_ptr() as _) == 0
+    }
+}
+
+/// Hashes the message to the point of G2 as specified by `hash_to_curve` of RFC 9380
+fn hash_to_g2(message: &[u8]) -> Option<Vec<u8>> {
+    let field_elements = hash_to_field(message, DST);
+    let points = call_host_function(host::bls12381_map_fp2_to_g2, &field_elements)?;
+
+    let mut sum_input = Vec::with_capacity(points.len() + 2);
+    for point in points.chunks(4 * FP_BYTES_LEN) {
+        sum_input.push(0);
+        sum_input.extend_from_slice(point);
+    }
+    call_host_function(host::bls12381_p2_sum, &sum_input)
+}
+
+/// `hash_to_field` of RFC 9380 producing two Fp2 elements.
+/// The Fp2 elements are encoded as `c1 || c0` as expected by the host functions
+fn hash_to_field(message: &[u8], dst: &[u8]) -> Vec<u8> {
+    let uniform_bytes = expand_message_xmd(message, dst, 4 * HASH_TO_FIELD_BYTES_LEN);
+
+    let mut field_elements = Vec::with_capacity(4 * FP_BYTES_LEN);
+    for element in uniform_bytes.chunks(2 * HASH_TO_FIELD_BYTES_LEN) {
+        let (c0, c1) = element.split_at(HASH_TO_FIELD_BYTES_LEN);
+        field_elements.extend_from_slice(&reduce_modulo_p(c1));
+        field_elements.extend_from_slice(&reduce_modulo_p(c0));
+    }
+    field_elements
+}
+
+/// `expand_message_xmd` of RFC 9380 with SHA-256
+fn expand_message_xmd(message: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    const HASH_BYTES_LEN: usize = 32;
+    const BLOCK_BYTES_LEN: usize = 64;
+
+    // The requested length is always a multiple of the hash length
+    let ell = len_in_bytes / HASH_BYTES_LEN;
+    let mut dst_prime = dst.to_vec();
+    dst_prime.push(dst.len() as u8);
+
+    let mut message_prime = vec![0u8; BLOCK_BYTES_LEN];
+    message_prime.extend_from_slice(message);
+    message_prime.extend_from_slice(&(len_in_bytes as u16).to_be_bytes());
+    message_prime.push(0);
+    message_prime.extend_from_slice(&dst_prime);
+    let b_0 = env::sha256(&message_prime);
+
+    let mut b_i = env::sha256(&[b_0.as_slice(), &[1], &dst_prime].concat());
+    let mut uniform_bytes = b_i.clone();
+    for i in 2..=ell {
+        let mut input: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+        input.push(i as u8);
+        input.extend_from_slice(&dst_prime);
+        b_i = env::sha256(&input);
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+
+    uniform_bytes
+}
+
+/// Reduces the big-endian number modulo the base field modulus
+fn reduce_modulo_p(value: &[u8]) -> [u8; FP_BYTES_LEN] {
+    let mut result = [0u64; 6];
+    for byte in value {
+        for bit in (0..8).rev() {
+            let mut carry = ((byte >> bit) & 1) as u64;
+            for limb in result.iter_mut() {
+                let next_carry = *limb >> 63;
+                *limb = (*limb << 1) | carry;
+                carry = next_carry;
+            }
+
+            if !is_less_than_modulus(&result) {
+                subtract_modulus(&mut result);
+            }
+        }
+    }
+
+    let mut bytes = [0u8; FP_BYTES_LEN];
+    for (chunk, limb) in bytes.chunks_mut(8).zip(result.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+}
+
+fn is_less_than_modulus(value: &[u64; 6]) -> bool {
+    for (limb, modulus_limb) in value.iter().zip(MODULUS.iter()).rev() {
+        if limb != modulus_limb {
+            return limb < modulus_limb;
+        }
+    }
+    false
+}
+
+fn subtract_modulus(value: &mut [u64; 6]) {
+    let mut borrow = false;
+    for (limb, modulus_limb) in value.iter_mut().zip(MODULUS.iter()) {
+        let (result, overflow_1) = limb.overflowing_sub(*modulus_limb);
+        let (result, overflow_2) = result.overflowing_sub(borrow as u64);
+        *limb = result;
+        borrow = overflow_1 || overflow_2;
+    }
+}
+
+fn call_host_function(
+    host_function: unsafe extern "C" fn(u64, u64, u64) -> u64,
+    input: &[u8],
+) -> Option<Vec<u8>> {
+    let status = unsafe { host_function(input.len() as _, input.as_ptr() as _, REGISTER_ID) };
+    if status != 0 {
+        return None;
+    }
+    env::read_register(REGISTER_ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The test vectors of RFC 9380, appendix K.1
+    #[test]
+    fn test_expand_message_xmd() {
+        const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let test_vectors: [(&[u8], usize, &str); 5] = [
+            (
+                b"",
+                0x20,
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+            ),
+            (
+                b"abc",
+                0x20,
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+            ),
+            (
+                b"abcdef0123456789",
+                0x20,
+                "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+            ),
+            (
+                b"",
+                0x80,
+                "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced",
+            ),
+            (
+                b"abc",
+                0x80,
+                "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40",
+            ),
+        ];
+
+        for (message, len_in_bytes, uniform_bytes) in test_vectors {
+            assert_eq!(
+                hex::encode(expand_message_xmd(message, DST, len_in_bytes)),
+                uniform_bytes
+            );
+        }
+    }
+
+    // The `u` values of the BLS12381G2_XMD:SHA-256_SSWU_RO_ suite test vectors of RFC 9380, appendix J.10.1
+    #[test]
+    fn test_hash_to_field() {
+        const DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let test_vectors: [(&[u8], [&str; 4]); 2] = [
+            (
+                b"",
+                [
+                    "03dbc2cce174e91ba93cbb08f26b917f98194a2ea08d1cce75b2b9cc9f21689d80bd79b594a613d0a68eb807dfdc1cf8",
+                    "05a2acec64114845711a54199ea339abd125ba38253b70a92c876df10598bd1986b739cad67961eb94f7076511b3b39a",
+                    "02f99798e8a5acdeed60d7e18e9120521ba1f47ec090984662846bc825de191b5b7641148c0dbc237726a334473eee94",
+                    "145a81e418d4010cc027a68f14391b30074e89e60ee7a22f87217b2f6eb0c4b94c9115b436e6fa4607e95a98de30a435",
+                ],
+            ),
+            (
+                b"abc",
+                [
+                    "15f7c0aa8f6b296ab5ff9c2c7581ade64f4ee6f1bf18f55179ff44a2cf355fa53dd2a2158c5ecb17d7c52f63e7195771",
+                    "01c8067bf4c0ba709aa8b9abc3d1cef589a4758e09ef53732d670fd8739a7274e111ba2fcaa71b3d33df2a3a0c8529dd",
+                    "187111d5e088b6b9acfdfad078c4dacf72dcd17ca17c82be35e79f8c372a693f60a033b461d81b025864a0ad051a06e4",
+                    "08b852331c96ed983e497ebc6dee9b75e373d923b729194af8e72a051ea586f3538a6ebb1e80881a082fa2b24df9f566",
+                ],
+            ),
+        ];
+
+        for (message, [u0_c0, u0_c1, u1_c0, u1_c1]) in test_vectors {
+            assert_eq!(
+                hex::encode(hash_to_field(message, DST)),
+                [u0_c1, u0_c0, u1_c1, u1_c0].concat()
+            );
+        }
+    }
+}
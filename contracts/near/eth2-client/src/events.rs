@@ -0,0 +1,71 @@
+use eth_types::events::NearEvent;
+use near_sdk::json_types::U128;
+use near_sdk::serde::Serialize;
+use near_sdk::AccountId;
+
+use crate::ParameterChange;
+
+/// Events emitted by the client in the NEP-297 format:
+/// `EVENT_JSON:{"standard":"eth2-client","version":"1.0.0","event":"...","data":{...}}`
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(
+    crate = "near_sdk::serde",
+    tag = "event",
+    content = "data",
+    rename_all = "snake_case"
+)]
+pub enum Eth2ClientEvent {
+    LightClientUpdateCommitted {
+        old_finalized_slot: u64,
+        new_finalized_slot: u64,
+        old_period: u64,
+        new_period: u64,
+    },
+    SyncCommitteeRotated {
+        period: u64,
+    },
+    /// The best valid update is applied without the finality
+    LightClientUpdateForced {
+        attested_slot: u64,
+        signature_slot: u64,
+    },
+    /// The sync committee signed two different finalized headers for the same slot.
+    /// The submission methods are paused until the DAO resolves the conflict
+    ConflictingFinalityDetected {
+        slot: u64,
+        first_finalized_root: String,
+        second_finalized_root: String,
+    },
+    /// The execution blocks in the range `[start_block_number, end_block_number]` are finalized
+    ExecutionHeadersFinalized {
+        start_block_number: u64,
+        end_block_number: u64,
+    },
+    /// The relayer is paid from the reward pool
+    RewardPaid {
+        account_id: AccountId,
+        amount: U128,
+    },
+    /// The DAO parameter change can be applied after the `apply_after` block timestamp
+    ParameterChangeStaged {
+        id: u64,
+        change: ParameterChange,
+        apply_after: u64,
+    },
+    ParameterChangeApplied {
+        id: u64,
+    },
+    ParameterChangeCancelled {
+        id: u64,
+    },
+    /// The execution blocks in the range `[start_block_number, end_block_number]` are removed
+    GcApplied {
+        start_block_number: u64,
+        end_block_number: u64,
+    },
+}
+
+impl NearEvent for Eth2ClientEvent {
+    const STANDARD: &'static str = "eth2-client";
+    const VERSION: &'static str = "1.0.0";
+}
@@ -11,13 +11,45 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use eth2_utility::consensus::*;
 use eth2_utility::types::*;
 use eth_types::eth2::*;
-use eth_types::{BlockHeader, H256};
-use near_sdk::collections::{LazyOption, LookupMap};
+use eth_types::{BlockHeader, BlockHeaderRoots, H256};
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::U128;
 use near_sdk::{
-    env, near_bindgen, require, AccountId, BorshStorageKey, PanicOnDefault, Promise, PublicKey,
+    env, near_bindgen, require, AccountId, Balance, BorshStorageKey, PanicOnDefault, Promise,
+    PublicKey,
 };
 use tree_hash::TreeHash;
 
+use crate::events::Eth2ClientEvent;
+use eth_types::events::NearEvent;
+
+/// The maximum number of the execution blocks removed by the gc on each header submission
+const MAX_GC_BLOCKS_PER_SUBMISSION: u64 = 32;
+
+/// The maximum number of the execution blocks removed by the gc on each checkpoint submission
+/// in the sparse mode, which covers the checkpoints submitted up to 8 epochs apart
+const MAX_GC_BLOCKS_PER_SPARSE_SUBMISSION: u64 = 256;
+
+/// The methods with the `#[pause]` attribute, the pause keys are the method names.
+/// All of them are paused on the conflict evidence
+const PAUSABLE_SUBMISSION_METHODS: [&str; 6] = [
+    "submit_beacon_chain_light_client_update",
+    "submit_beacon_chain_light_client_updates",
+    "submit_beacon_chain_best_light_client_update",
+    "force_light_client_update",
+    "approve_light_client_update",
+    "submit_execution_header",
+];
+
+/// The maximum number of the light client updates in one `submit_beacon_chain_light_client_updates` call
+const MAX_LIGHT_CLIENT_UPDATES_PER_SUBMISSION: usize = 4;
+
+/// The default delay of the DAO parameter changes, a day in nanoseconds
+pub const DEFAULT_PARAMETER_CHANGE_DELAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+#[cfg(feature = "bls_host_functions")]
+mod bls_host_functions;
+mod events;
 mod migrate;
 #[cfg(test)]
 mod tests;
@@ -30,6 +62,49 @@ enum StorageKey {
     FinalizedExecutionHeader,
     CurrentSyncCommittee,
     NextSyncCommittee,
+    FinalizedHeaderRoots,
+    BestValidUpdate,
+    SignerCommittee,
+    PendingLightClientUpdates,
+    RelayerPayouts,
+    PendingParameterChanges,
+}
+
+/// The rewards paid from the reward pool to the relayers
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardConfig {
+    /// Paid for each accepted light client update
+    pub light_client_update_reward: U128,
+    /// Paid for the execution header which finalizes the submitted headers batch
+    pub execution_headers_reward: U128,
+    /// The total amount paid within a NEAR epoch
+    pub max_rewards_per_epoch: U128,
+}
+
+/// The light client update waiting for the approvals of the signer committee
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PendingLightClientUpdate {
+    pub update: LightClientUpdate,
+    pub approvals: Vec<AccountId>,
+}
+
+/// The DAO parameter change which takes effect only after `parameter_change_delay`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ParameterChange {
+    TrustedSigner(Option<AccountId>),
+    TrustedBlocksSubmitter(Option<AccountId>),
+    HashesGcThreshold(u64),
+    ParameterChangeDelay(u64),
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingParameterChange {
+    pub change: ParameterChange,
+    /// The block timestamp in nanoseconds after which the change can be applied
+    pub apply_after: u64,
 }
 
 #[derive(AccessControlRole, Deserialize, Serialize, Copy, Clone)]
@@ -70,8 +145,8 @@ pub struct Eth2Client {
     /// It is desirable that this number is larger than 7 days' worth of headers, which is roughly
     /// 51k Ethereum blocks. So this number should be 51k in production.
     hashes_gc_threshold: u64,
-    /// Network. e.g. mainnet, goerli
-    network: Network,
+    /// Network parameters: genesis validators root and fork schedule
+    network_config: NetworkConfig,
     /// Hashes of the finalized execution blocks mapped to their numbers. Stores up to `hashes_gc_threshold` entries.
     /// Execution block number -> execution block hash
     finalized_execution_blocks: LookupMap<u64, H256>,
@@ -84,6 +159,40 @@ pub struct Eth2Client {
     unfinalized_head_execution_header: Option<ExecutionHeaderInfo>,
     unfinalized_tail_execution_header: Option<ExecutionHeaderInfo>,
     trusted_blocks_submitter: Option<AccountId>,
+    /// If set, only the execution blocks of the finalized checkpoints are stored, the other blocks
+    /// are proven on demand with `recent_block_hash_safe` and `historical_block_hash_safe`
+    sparse_mode: bool,
+    /// Whether the client stores `BlockHeaderRoots` of the submitted execution blocks
+    store_header_roots: bool,
+    /// Receipts root, state root and timestamp of the finalized execution blocks mapped to their numbers.
+    /// Filled only if `store_header_roots` is set and cleaned up together with `finalized_execution_blocks`
+    finalized_header_roots: LookupMap<u64, BlockHeaderRoots>,
+    /// The number of the oldest execution block which is not yet removed by the gc
+    gc_cursor: u64,
+    /// The non-finalized update with the most sync committee participants received since
+    /// the last committed update. Can be applied with `force_light_client_update`
+    best_valid_update: LazyOption<LightClientUpdate>,
+    /// If the threshold is set, the light client update is committed only after it's submitted
+    /// or approved by `signer_committee_threshold` distinct members of the committee.
+    /// Takes precedence over `trusted_signer`
+    signer_committee: UnorderedSet<AccountId>,
+    signer_committee_threshold: u32,
+    /// The updates submitted by the committee members mapped to their hashes. Each member approves
+    /// at most one of them. Cleaned up on each committed update and on each change of the committee
+    pending_light_client_updates: UnorderedMap<H256, PendingLightClientUpdate>,
+    /// The funds deposited with `deposit_reward_pool` and not yet paid to the relayers
+    reward_pool_balance: Balance,
+    reward_config: RewardConfig,
+    /// The NEAR epoch of the last payout and the total amount paid within it
+    rewards_epoch_height: u64,
+    rewards_paid_in_epoch: Balance,
+    /// The total amount paid to each relayer
+    relayer_payouts: LookupMap<AccountId, Balance>,
+    /// The delay in nanoseconds between staging and applying the DAO parameter change
+    parameter_change_delay: u64,
+    /// The staged parameter changes mapped to their ids
+    pending_parameter_changes: UnorderedMap<u64, PendingParameterChange>,
+    next_parameter_change_id: u64,
 }
 
 #[near_bindgen]
@@ -91,8 +200,14 @@ impl Eth2Client {
     #[init]
     #[private]
     pub fn init(#[serializer(borsh)] args: InitInput) -> Self {
-        let network =
-            Network::from_str(args.network.as_str()).unwrap_or_else(|e| env::panic_str(e.as_str()));
+        let network_config = args.network_config.unwrap_or_else(|| {
+            let network = Network::from_str(args.network.as_str())
+                .unwrap_or_else(|e| env::panic_str(e.as_str()));
+            NetworkConfig::new(&network)
+        });
+        network_config
+            .validate()
+            .unwrap_or_else(|e| env::panic_str(e.as_str()));
 
         #[cfg(feature = "mainnet")]
         {
@@ -102,7 +217,8 @@ impl Eth2Client {
             );
 
             require!(
-                (cfg!(feature = "bls") && args.verify_bls_signatures)
+                ((cfg!(feature = "bls") || cfg!(feature = "bls_host_functions"))
+                    && args.verify_bls_signatures)
                     || args.trusted_signer.is_some(),
                 "The client can't be executed in the trustless mode without BLS sigs verification on Mainnet"
             );
@@ -128,7 +244,7 @@ impl Eth2Client {
             validate_updates: args.validate_updates,
             verify_bls_signatures: args.verify_bls_signatures,
             hashes_gc_threshold: args.hashes_gc_threshold,
-            network,
+            network_config,
             finalized_execution_blocks: LookupMap::new(StorageKey::FinalizedExecutionBlocks),
             finalized_beacon_header: args.finalized_beacon_header,
             finalized_execution_header: LazyOption::new(
@@ -147,12 +263,40 @@ impl Eth2Client {
             unfinalized_head_execution_header: None,
             unfinalized_tail_execution_header: None,
             trusted_blocks_submitter: None,
+            sparse_mode: args.sparse_mode,
+            store_header_roots: args.store_header_roots,
+            finalized_header_roots: LookupMap::new(StorageKey::FinalizedHeaderRoots),
+            gc_cursor: args.finalized_execution_header.number,
+            best_valid_update: LazyOption::new(StorageKey::BestValidUpdate, None),
+            signer_committee: UnorderedSet::new(StorageKey::SignerCommittee),
+            signer_committee_threshold: 0,
+            pending_light_client_updates: UnorderedMap::new(StorageKey::PendingLightClientUpdates),
+            reward_pool_balance: 0,
+            reward_config: RewardConfig {
+                light_client_update_reward: U128(0),
+                execution_headers_reward: U128(0),
+                max_rewards_per_epoch: U128(0),
+            },
+            rewards_epoch_height: 0,
+            rewards_paid_in_epoch: 0,
+            relayer_payouts: LookupMap::new(StorageKey::RelayerPayouts),
+            parameter_change_delay: args
+                .parameter_change_delay
+                .unwrap_or(DEFAULT_PARAMETER_CHANGE_DELAY),
+            pending_parameter_changes: UnorderedMap::new(StorageKey::PendingParameterChanges),
+            next_parameter_change_id: 0,
         };
 
         contract.finalized_execution_blocks.insert(
             &args.finalized_execution_header.number,
             &finalized_execution_header_hash,
         );
+        if contract.store_header_roots {
+            contract.finalized_header_roots.insert(
+                &args.finalized_execution_header.number,
+                &BlockHeaderRoots::from(&args.finalized_execution_header),
+            );
+        }
 
         contract.acl_init_super_admin(env::predecessor_account_id());
         contract
@@ -178,6 +322,204 @@ impl Eth2Client {
         self.finalized_execution_blocks.get(&block_number)
     }
 
+    /// Same as `block_hash_safe`, but for each of `block_numbers`
+    #[result_serializer(borsh)]
+    pub fn block_hashes_safe(
+        &self,
+        #[serializer(borsh)] block_numbers: Vec<u64>,
+    ) -> Vec<Option<H256>> {
+        block_numbers
+            .into_iter()
+            .map(|block_number| self.block_hash_safe(block_number))
+            .collect()
+    }
+
+    /// Returns receipts root, state root and timestamp of the finalized execution block.
+    /// Available only for the blocks submitted while `store_header_roots` is set
+    #[result_serializer(borsh)]
+    pub fn header_roots_safe(
+        &self,
+        #[serializer(borsh)] block_number: u64,
+    ) -> Option<BlockHeaderRoots> {
+        if block_number > self.finalized_execution_header.get().unwrap().block_number {
+            return None;
+        }
+        self.finalized_header_roots.get(&block_number)
+    }
+
+    /// Returns the execution block hash proven by `proof` against the `block_roots`
+    /// of the finalized beacon state, or `None` if the proof is invalid.
+    /// Allows to verify the blocks from the last `SLOTS_PER_HISTORICAL_ROOT` slots which are
+    /// not stored by the client, e.g. in the sparse mode.
+    #[result_serializer(borsh)]
+    pub fn recent_block_hash_safe(
+        &self,
+        #[serializer(borsh)] proof: BlockRootsProof,
+    ) -> Option<H256> {
+        let header_update = &proof.header_update;
+        if !self
+            .network_config
+            .validate_beacon_block_header_update(header_update)
+        {
+            return None;
+        }
+
+        let finalized_header = &self.finalized_beacon_header.header;
+        if !self.network_config.verify_recent_block_root(
+            H256(header_update.beacon_header.tree_hash_root()),
+            header_update.beacon_header.slot,
+            &proof.block_roots_branch,
+            finalized_header.slot,
+            finalized_header.state_root,
+        ) {
+            return None;
+        }
+
+        Some(header_update.execution_block_hash)
+    }
+
+    /// Returns the execution block hash proven by `proof` against the `historical_summaries`
+    /// of the finalized beacon state, or `None` if the proof is invalid.
+    /// Allows to verify the blocks which are already removed from `finalized_execution_blocks`.
+    #[result_serializer(borsh)]
+    pub fn historical_block_hash_safe(
+        &self,
+        #[serializer(borsh)] proof: HistoricalBlockProof,
+    ) -> Option<H256> {
+        let header_update = &proof.header_update;
+        if !self
+            .network_config
+            .validate_beacon_block_header_update(header_update)
+        {
+            return None;
+        }
+
+        let finalized_header = &self.finalized_beacon_header.header;
+        if !self.network_config.verify_historical_block_root(
+            H256(header_update.beacon_header.tree_hash_root()),
+            header_update.beacon_header.slot,
+            &proof.block_roots_branch,
+            &proof.historical_summary_branch,
+            finalized_header.slot,
+            finalized_header.state_root,
+        ) {
+            return None;
+        }
+
+        Some(header_update.execution_block_hash)
+    }
+
+    /// Returns the EIP-4844 versioned hash of the blob whose KZG commitment is proven by `proof`
+    /// to be included in the beacon block, or `None` if the proof is invalid.
+    /// The beacon block should be either the finalized one or proven against the `block_roots`
+    /// of the finalized beacon state, so the blob belongs to the execution block
+    /// `proof.header_update.execution_block_hash`.
+    #[result_serializer(borsh)]
+    pub fn blob_versioned_hash_safe(
+        &self,
+        #[serializer(borsh)] proof: BlobCommitmentProof,
+    ) -> Option<H256> {
+        let header_update = &proof.header_update;
+        if !self
+            .network_config
+            .validate_beacon_block_header_update(header_update)
+        {
+            return None;
+        }
+
+        let beacon_block_root = H256(header_update.beacon_header.tree_hash_root());
+        let finalized_header = &self.finalized_beacon_header.header;
+        if beacon_block_root != self.finalized_beacon_header.beacon_block_root
+            && !self.network_config.verify_recent_block_root(
+                beacon_block_root,
+                header_update.beacon_header.slot,
+                &proof.block_roots_branch,
+                finalized_header.slot,
+                finalized_header.state_root,
+            )
+        {
+            return None;
+        }
+
+        if !self.network_config.verify_blob_kzg_commitment(
+            &proof.blob_kzg_commitment,
+            proof.blob_index,
+            &proof.commitment_branch,
+            header_update.beacon_header.slot,
+            header_update.beacon_header.body_root,
+        ) {
+            return None;
+        }
+
+        Some(kzg_commitment_to_versioned_hash(&proof.blob_kzg_commitment))
+    }
+
+    /// Verifies that `leaf` is the node with the generalized index `gindex` in the finalized
+    /// beacon state. The layout of the state depends on the fork at `finalized_beacon_block_slot`
+    #[result_serializer(borsh)]
+    pub fn verify_beacon_state_proof(
+        &self,
+        #[serializer(borsh)] gindex: u64,
+        #[serializer(borsh)] leaf: H256,
+        #[serializer(borsh)] branch: Vec<H256>,
+    ) -> bool {
+        verify_merkle_proof_by_gindex(
+            leaf,
+            &branch,
+            gindex,
+            self.finalized_beacon_header.header.state_root,
+        )
+    }
+
+    /// Verifies that `validator` is the record of `validator_index` in the finalized beacon state
+    #[result_serializer(borsh)]
+    pub fn verify_validator(
+        &self,
+        #[serializer(borsh)] validator_index: u64,
+        #[serializer(borsh)] validator: Validator,
+        #[serializer(borsh)] validator_branch: Vec<H256>,
+    ) -> bool {
+        let finalized_header = &self.finalized_beacon_header.header;
+        match self
+            .network_config
+            .validator_gindex(validator_index, finalized_header.slot)
+        {
+            Some(gindex) => verify_merkle_proof_by_gindex(
+                H256(validator.tree_hash_root()),
+                &validator_branch,
+                gindex,
+                finalized_header.state_root,
+            ),
+            None => false,
+        }
+    }
+
+    /// Returns the balance in Gwei of `validator_index` in the finalized beacon state, or `None`
+    /// if the proof is invalid. `balances_chunk` is the chunk of the `balances` list with the
+    /// balances of the four validators starting from `validator_index / 4 * 4`
+    #[result_serializer(borsh)]
+    pub fn validator_balance_safe(
+        &self,
+        #[serializer(borsh)] validator_index: u64,
+        #[serializer(borsh)] balances_chunk: H256,
+        #[serializer(borsh)] balance_branch: Vec<H256>,
+    ) -> Option<u64> {
+        let finalized_header = &self.finalized_beacon_header.header;
+        let gindex = self
+            .network_config
+            .balances_chunk_gindex(validator_index, finalized_header.slot)?;
+        if !verify_merkle_proof_by_gindex(
+            balances_chunk,
+            &balance_branch,
+            gindex,
+            finalized_header.state_root,
+        ) {
+            return None;
+        }
+
+        Some(get_balance_from_chunk(&balances_chunk, validator_index))
+    }
+
     /// Checks if the execution header is already submitted.
     #[result_serializer(borsh)]
     pub fn is_known_execution_header(&self, block_number: u64) -> bool {
@@ -218,6 +560,18 @@ impl Eth2Client {
         self.client_mode.clone()
     }
 
+    /// Returns the network config the client was initialized with
+    #[result_serializer(borsh)]
+    pub fn get_network_config(&self) -> NetworkConfig {
+        self.network_config.clone()
+    }
+
+    /// Returns the best valid update which can be forced by `force_light_client_update`
+    #[result_serializer(borsh)]
+    pub fn get_best_valid_update(&self) -> Option<LightClientUpdate> {
+        self.best_valid_update.get()
+    }
+
     /// Returns unfinalized tail execution block number
     #[result_serializer(borsh)]
     pub fn get_unfinalized_tail_block_number(&self) -> Option<u64> {
@@ -237,7 +591,170 @@ impl Eth2Client {
             self.validate_light_client_update(&update);
         }
 
+        if self.is_signer_committee_enabled() {
+            let update_hash = H256::from(env::keccak256(&update.try_to_vec().unwrap()));
+            let pending_update = self
+                .pending_light_client_updates
+                .get(&update_hash)
+                .unwrap_or(PendingLightClientUpdate {
+                    update,
+                    approvals: vec![],
+                });
+            self.approve_pending_light_client_update(update_hash, pending_update);
+            return;
+        }
+
         self.commit_light_client_update(update);
+        self.pay_reward(self.reward_config.light_client_update_reward.0);
+    }
+
+    /// Approves the update submitted by another member of the signer committee.
+    /// The previous approval of the member, if any, is withdrawn. The member whose approval
+    /// commits the update receives the light client update reward
+    #[pause(except(roles(Role::UnrestrictedSubmitLightClientUpdate, Role::DAO)))]
+    pub fn approve_light_client_update(&mut self, #[serializer(borsh)] update_hash: H256) {
+        self.is_light_client_update_allowed();
+        require!(
+            self.is_signer_committee_enabled(),
+            "The signer committee is not set"
+        );
+
+        let pending_update = self
+            .pending_light_client_updates
+            .get(&update_hash)
+            .unwrap_or_else(|| env::panic_str("The update is not pending"));
+        self.approve_pending_light_client_update(update_hash, pending_update);
+    }
+
+    /// Submits the update whose active header is the attested header instead of the finalized one.
+    /// It's stored as the best valid update if it has more sync committee participants than
+    /// the stored one. Used to keep the client updated during the long periods of non-finality
+    #[pause(except(roles(Role::UnrestrictedSubmitLightClientUpdate, Role::DAO)))]
+    pub fn submit_beacon_chain_best_light_client_update(
+        &mut self,
+        #[serializer(borsh)] update: LightClientUpdate,
+    ) {
+        self.is_light_client_update_allowed();
+        require!(
+            !self.is_signer_committee_enabled(),
+            "The best update is not supported with the signer committee"
+        );
+
+        if self.validate_updates {
+            self.validate_best_light_client_update(&update);
+        }
+
+        if let Some(best_valid_update) = self.best_valid_update.get() {
+            require!(
+                get_sync_committee_bits_sum(&update)
+                    > get_sync_committee_bits_sum(&best_valid_update),
+                "The update doesn't have more participants than the best valid update"
+            );
+        }
+
+        self.best_valid_update.set(&update);
+    }
+
+    /// Applies the best valid update as if it were finalized. Can be called by the DAO at any time
+    /// or by anyone if the best valid update is signed more than `UPDATE_TIMEOUT` slots after
+    /// the finalized header
+    #[pause(except(roles(Role::UnrestrictedSubmitLightClientUpdate, Role::DAO)))]
+    pub fn force_light_client_update(&mut self) {
+        require!(self.client_mode == ClientMode::SubmitLightClientUpdate);
+        require!(
+            !self.is_signer_committee_enabled(),
+            "The forced update is not supported with the signer committee"
+        );
+
+        let best_valid_update = self
+            .best_valid_update
+            .get()
+            .unwrap_or_else(|| env::panic_str("The best valid update is missing"));
+        require!(
+            best_valid_update.signature_slot
+                > self.finalized_beacon_header.header.slot + UPDATE_TIMEOUT
+                || self.acl_has_role(Role::DAO.into(), env::predecessor_account_id()),
+            "The update timeout has not passed yet"
+        );
+
+        Eth2ClientEvent::LightClientUpdateForced {
+            attested_slot: best_valid_update.attested_beacon_header.slot,
+            signature_slot: best_valid_update.signature_slot,
+        }
+        .emit();
+
+        self.commit_light_client_update(best_valid_update);
+    }
+
+    /// Accepts the evidence that the sync committee signed two different finalized headers
+    /// for the same slot. Can be submitted by anyone. Both updates are verified, then
+    /// the submission methods are paused and the alert event is emitted for the DAO
+    pub fn submit_conflict_evidence(
+        &mut self,
+        #[serializer(borsh)] first_update: LightClientUpdate,
+        #[serializer(borsh)] second_update: LightClientUpdate,
+    ) {
+        require!(
+            cfg!(any(feature = "bls", feature = "bls_host_functions"))
+                && self.verify_bls_signatures,
+            "The conflict evidence can't be verified without the BLS signatures verification"
+        );
+
+        let first_header = &first_update.finality_update.header_update.beacon_header;
+        let second_header = &second_update.finality_update.header_update.beacon_header;
+        require!(
+            first_header.slot == second_header.slot,
+            "The finalized headers of the evidence should have the same slot"
+        );
+        let first_finalized_root = H256(first_header.tree_hash_root());
+        let second_finalized_root = H256(second_header.tree_hash_root());
+        require!(
+            first_finalized_root != second_finalized_root,
+            "The finalized headers of the evidence are not conflicting"
+        );
+
+        self.verify_conflict_evidence_update(&first_update);
+        self.verify_conflict_evidence_update(&second_update);
+
+        self.pause_submissions();
+        Eth2ClientEvent::ConflictingFinalityDetected {
+            slot: first_header.slot,
+            first_finalized_root: format!("{:#x}", first_finalized_root.0),
+            second_finalized_root: format!("{:#x}", second_finalized_root.0),
+        }
+        .emit();
+    }
+
+    /// Submits the chain of light client updates in one call, e.g. to catch up several sync
+    /// committee periods. Each update is validated against the state committed by the previous one,
+    /// so every update has to be from the same or the next period as the previous update.
+    #[pause(except(roles(Role::UnrestrictedSubmitLightClientUpdate, Role::DAO)))]
+    pub fn submit_beacon_chain_light_client_updates(
+        &mut self,
+        #[serializer(borsh)] updates: Vec<LightClientUpdate>,
+    ) {
+        require!(!updates.is_empty(), "The list of updates is empty");
+        require!(
+            updates.len() <= MAX_LIGHT_CLIENT_UPDATES_PER_SUBMISSION,
+            format!(
+                "The list of updates is too long, max {} updates are allowed",
+                MAX_LIGHT_CLIENT_UPDATES_PER_SUBMISSION
+            )
+        );
+        require!(
+            !self.is_signer_committee_enabled(),
+            "The updates batch is not supported with the signer committee"
+        );
+        self.is_light_client_update_allowed();
+
+        for update in updates {
+            if self.validate_updates {
+                self.validate_light_client_update(&update);
+            }
+
+            self.commit_light_client_update(update);
+            self.pay_reward(self.reward_config.light_client_update_reward.0);
+        }
     }
 
     #[result_serializer(borsh)]
@@ -275,26 +792,54 @@ impl Eth2Client {
             format!("The block {:#?} already submitted!", &block_hash)
         );
 
-        let finalized_execution_header = self.finalized_execution_header.get().unwrap();
-        // Apply gc
-        if let Some(diff_between_unfinalized_head_and_tail) =
-            self.get_diff_between_unfinalized_head_and_tail()
-        {
-            let header_number_to_remove = (finalized_execution_header.block_number
-                + diff_between_unfinalized_head_and_tail)
-                .checked_sub(self.hashes_gc_threshold)
-                .unwrap_or(0);
+        if self.store_header_roots {
+            self.finalized_header_roots
+                .insert(&block_header.number, &BlockHeaderRoots::from(&block_header));
+        }
 
-            require!(
-                header_number_to_remove < finalized_execution_header.block_number,
-                "The `hashes_gc_threshold` is not enough to be able to apply gc correctly"
+        if self.sparse_mode {
+            let prev_finalized_block_number =
+                self.finalized_execution_header.get().unwrap().block_number;
+            // Only the block of the finalized checkpoint is submitted in the sparse mode
+            self.finalized_execution_header.set(&ExecutionHeaderInfo {
+                parent_hash: block_header.parent_hash,
+                block_number: block_header.number,
+                submitter: env::predecessor_account_id(),
+            });
+            self.client_mode = ClientMode::SubmitLightClientUpdate;
+            // Apply gc to as many blocks as the finalized block moved, so the gc backlog doesn't grow
+            self.gc_finalized_execution_blocks(
+                block_header
+                    .number
+                    .saturating_sub(prev_finalized_block_number)
+                    .clamp(
+                        MAX_GC_BLOCKS_PER_SUBMISSION,
+                        MAX_GC_BLOCKS_PER_SPARSE_SUBMISSION,
+                    ),
             );
 
-            if header_number_to_remove > 0 {
-                self.gc_finalized_execution_blocks(header_number_to_remove);
+            Eth2ClientEvent::ExecutionHeadersFinalized {
+                start_block_number: block_header.number,
+                end_block_number: block_header.number,
             }
+            .emit();
+            self.pay_reward(self.reward_config.execution_headers_reward.0);
+
+            #[cfg(feature = "logs")]
+            env::log_str(
+                format!(
+                    "Submitted finalized header number {}, hash {:#?}",
+                    block_header.number, block_hash
+                )
+                .as_str(),
+            );
+            return;
         }
 
+        let finalized_execution_header = self.finalized_execution_header.get().unwrap();
+        // Apply gc
+        self.gc_finalized_execution_blocks(MAX_GC_BLOCKS_PER_SUBMISSION);
+
         if block_header.number == finalized_execution_header.block_number + 1 {
             let finalized_execution_header_hash = self
                 .finalized_execution_blocks
@@ -318,11 +863,22 @@ impl Eth2Client {
                 .as_str(),
             );
 
+            Eth2ClientEvent::ExecutionHeadersFinalized {
+                start_block_number: block_header.number,
+                end_block_number: self
+                    .unfinalized_head_execution_header
+                    .as_ref()
+                    .unwrap()
+                    .block_number,
+            }
+            .emit();
+
             self.finalized_execution_header
                 .set(self.unfinalized_head_execution_header.as_ref().unwrap());
             self.unfinalized_tail_execution_header = None;
             self.unfinalized_head_execution_header = None;
             self.client_mode = ClientMode::SubmitLightClientUpdate;
+            self.pay_reward(self.reward_config.execution_headers_reward.0);
         } else {
             let block_info = ExecutionHeaderInfo {
                 parent_hash: block_header.parent_hash,
@@ -346,33 +902,246 @@ impl Eth2Client {
         );
     }
 
+    /// Stages the trusted signer change. Returns the id of the pending change
     #[access_control_any(roles(Role::DAO))]
-    pub fn update_trusted_signer(&mut self, trusted_signer: Option<AccountId>) {
-        self.trusted_signer = trusted_signer;
+    pub fn update_trusted_signer(&mut self, trusted_signer: Option<AccountId>) -> u64 {
+        self.stage_parameter_change(ParameterChange::TrustedSigner(trusted_signer))
     }
 
     pub fn get_trusted_signer(&self) -> Option<AccountId> {
         self.trusted_signer.clone()
     }
 
+    /// Adds the member to the signer committee. The pending updates are discarded
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_signer_committee_member(&mut self, account_id: AccountId) {
+        require!(
+            self.signer_committee.insert(&account_id),
+            "The account is already a member of the signer committee"
+        );
+        self.pending_light_client_updates.clear();
+    }
+
+    /// Removes the member from the signer committee. The pending updates are discarded
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_signer_committee_member(&mut self, account_id: AccountId) {
+        require!(
+            self.signer_committee.remove(&account_id),
+            "The account is not a member of the signer committee"
+        );
+        require!(
+            u64::from(self.signer_committee_threshold) <= self.signer_committee.len(),
+            "The threshold exceeds the number of the signer committee members"
+        );
+        self.pending_light_client_updates.clear();
+    }
+
+    /// Sets the number of the committee approvals required to commit the update.
+    /// The committee is disabled if the threshold is `0`. The pending updates are discarded,
+    /// as well as the best valid update if the committee is enabled
     #[access_control_any(roles(Role::DAO))]
-    pub fn update_trusted_blocks_submitter(&mut self, trusted_blocks_submitter: Option<AccountId>) {
-        self.trusted_blocks_submitter = trusted_blocks_submitter;
+    pub fn update_signer_committee_threshold(&mut self, threshold: u32) {
+        require!(
+            u64::from(threshold) <= self.signer_committee.len(),
+            "The threshold exceeds the number of the signer committee members"
+        );
+        self.signer_committee_threshold = threshold;
+        self.pending_light_client_updates.clear();
+        if self.is_signer_committee_enabled() {
+            self.best_valid_update.remove();
+        }
+    }
+
+    pub fn get_signer_committee(&self) -> Vec<AccountId> {
+        self.signer_committee.to_vec()
+    }
+
+    pub fn get_signer_committee_threshold(&self) -> u32 {
+        self.signer_committee_threshold
+    }
+
+    /// Returns the hashes of the pending updates with the members who approved them
+    #[result_serializer(borsh)]
+    pub fn get_pending_light_client_updates(&self) -> Vec<(H256, Vec<AccountId>)> {
+        self.pending_light_client_updates
+            .iter()
+            .map(|(update_hash, pending_update)| (update_hash, pending_update.approvals))
+            .collect()
+    }
+
+    #[result_serializer(borsh)]
+    pub fn get_pending_light_client_update(
+        &self,
+        #[serializer(borsh)] update_hash: H256,
+    ) -> Option<LightClientUpdate> {
+        self.pending_light_client_updates
+            .get(&update_hash)
+            .map(|pending_update| pending_update.update)
+    }
+
+    /// Adds the attached deposit to the reward pool. Can be funded by anyone
+    #[payable]
+    pub fn deposit_reward_pool(&mut self) {
+        let amount = env::attached_deposit();
+        require!(amount > 0, "The attached deposit should be positive");
+        self.reward_pool_balance += amount;
+    }
+
+    /// Withdraws the funds of the reward pool which are not paid to the relayers
+    #[access_control_any(roles(Role::DAO))]
+    pub fn withdraw_reward_pool(&mut self, amount: U128, receiver_id: AccountId) -> Promise {
+        require!(
+            amount.0 <= self.reward_pool_balance,
+            "The amount exceeds the reward pool balance"
+        );
+        self.reward_pool_balance -= amount.0;
+        Promise::new(receiver_id).transfer(amount.0)
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn update_reward_config(&mut self, reward_config: RewardConfig) {
+        self.reward_config = reward_config;
+    }
+
+    pub fn get_reward_config(&self) -> RewardConfig {
+        self.reward_config.clone()
+    }
+
+    pub fn get_reward_pool_balance(&self) -> U128 {
+        U128(self.reward_pool_balance)
+    }
+
+    /// Returns the amount paid within the current NEAR epoch
+    pub fn get_rewards_paid_in_epoch(&self) -> U128 {
+        if self.rewards_epoch_height != env::epoch_height() {
+            return U128(0);
+        }
+        U128(self.rewards_paid_in_epoch)
+    }
+
+    /// Returns the total amount paid to the relayer
+    pub fn get_relayer_payouts(&self, account_id: AccountId) -> U128 {
+        U128(self.relayer_payouts.get(&account_id).unwrap_or(0))
+    }
+
+    /// Stages the trusted blocks submitter change. Returns the id of the pending change
+    #[access_control_any(roles(Role::DAO))]
+    pub fn update_trusted_blocks_submitter(
+        &mut self,
+        trusted_blocks_submitter: Option<AccountId>,
+    ) -> u64 {
+        self.stage_parameter_change(ParameterChange::TrustedBlocksSubmitter(
+            trusted_blocks_submitter,
+        ))
     }
 
     pub fn get_trusted_blocks_submitter(&self) -> Option<AccountId> {
         self.trusted_blocks_submitter.clone()
     }
 
+    /// Switches the sparse mode. The mode can't be switched while the execution headers
+    /// are being submitted
     #[access_control_any(roles(Role::DAO))]
-    pub fn update_hashes_gc_threshold(&mut self, hashes_gc_threshold: u64) {
-        self.hashes_gc_threshold = hashes_gc_threshold;
+    pub fn update_sparse_mode(&mut self, sparse_mode: bool) {
+        require!(
+            self.client_mode == ClientMode::SubmitLightClientUpdate,
+            "The sparse mode can't be switched during the execution headers submission"
+        );
+        self.sparse_mode = sparse_mode;
+    }
+
+    pub fn is_sparse_mode(&self) -> bool {
+        self.sparse_mode
+    }
+
+    /// Switches the storing of the header roots. The roots of the blocks submitted while
+    /// the storing is disabled are not available in `header_roots_safe`
+    #[access_control_any(roles(Role::DAO))]
+    pub fn update_store_header_roots(&mut self, store_header_roots: bool) {
+        self.store_header_roots = store_header_roots;
+    }
+
+    pub fn is_store_header_roots(&self) -> bool {
+        self.store_header_roots
+    }
+
+    /// Removes up to `limit` of the execution blocks which are older than `hashes_gc_threshold`.
+    /// Can be called by anyone to drain the gc backlog. Returns the number of processed blocks
+    pub fn gc(&mut self, limit: u64) -> u64 {
+        self.gc_finalized_execution_blocks(limit)
+    }
+
+    /// Returns the number of the execution blocks which are waiting to be removed by the gc
+    pub fn get_gc_backlog(&self) -> u64 {
+        self.get_gc_target_block_number()
+            .map(|target| (target + 1).saturating_sub(self.gc_cursor))
+            .unwrap_or(0)
+    }
+
+    /// Stages the gc threshold change. Returns the id of the pending change
+    #[access_control_any(roles(Role::DAO))]
+    pub fn update_hashes_gc_threshold(&mut self, hashes_gc_threshold: u64) -> u64 {
+        self.stage_parameter_change(ParameterChange::HashesGcThreshold(hashes_gc_threshold))
     }
 
     pub fn get_hashes_gc_threshold(&self) -> u64 {
         self.hashes_gc_threshold
     }
 
+    /// Stages the delay change. The new delay is applied after the current one expires
+    #[access_control_any(roles(Role::DAO))]
+    pub fn update_parameter_change_delay(&mut self, parameter_change_delay: u64) -> u64 {
+        self.stage_parameter_change(ParameterChange::ParameterChangeDelay(
+            parameter_change_delay,
+        ))
+    }
+
+    pub fn get_parameter_change_delay(&self) -> u64 {
+        self.parameter_change_delay
+    }
+
+    pub fn get_pending_parameter_changes(&self) -> Vec<(u64, PendingParameterChange)> {
+        self.pending_parameter_changes.to_vec()
+    }
+
+    /// Applies the staged change once its delay has passed
+    #[access_control_any(roles(Role::DAO))]
+    pub fn apply_parameter_change(&mut self, id: u64) {
+        let pending_change = self
+            .pending_parameter_changes
+            .get(&id)
+            .unwrap_or_else(|| env::panic_str("The parameter change doesn't exist"));
+        require!(
+            env::block_timestamp() >= pending_change.apply_after,
+            "The parameter change delay hasn't passed yet"
+        );
+        self.pending_parameter_changes.remove(&id);
+
+        match pending_change.change {
+            ParameterChange::TrustedSigner(trusted_signer) => self.trusted_signer = trusted_signer,
+            ParameterChange::TrustedBlocksSubmitter(trusted_blocks_submitter) => {
+                self.trusted_blocks_submitter = trusted_blocks_submitter
+            }
+            ParameterChange::HashesGcThreshold(hashes_gc_threshold) => {
+                self.hashes_gc_threshold = hashes_gc_threshold
+            }
+            ParameterChange::ParameterChangeDelay(parameter_change_delay) => {
+                self.parameter_change_delay = parameter_change_delay
+            }
+        }
+
+        Eth2ClientEvent::ParameterChangeApplied { id }.emit();
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn cancel_parameter_change(&mut self, id: u64) {
+        require!(
+            self.pending_parameter_changes.remove(&id).is_some(),
+            "The parameter change doesn't exist"
+        );
+        Eth2ClientEvent::ParameterChangeCancelled { id }.emit();
+    }
+
     #[access_control_any(roles(Role::DAO))]
     pub fn attach_full_access_key(&self, public_key: PublicKey) -> Promise {
         Promise::new(env::current_account_id()).add_full_access_key(public_key)
@@ -388,11 +1157,34 @@ impl Eth2Client {
         let finalized_period =
             compute_sync_committee_period(self.finalized_beacon_header.header.slot);
         self.verify_finality_branch(update, finalized_period);
+        self.verify_sync_aggregate(update);
+    }
+
+    /// Validates the update without the finality. The active header of such update is
+    /// the attested header itself, so there is no finality branch to verify
+    fn validate_best_light_client_update(&self, update: &LightClientUpdate) {
+        require!(
+            update
+                .finality_update
+                .header_update
+                .beacon_header
+                .tree_hash_root()
+                == update.attested_beacon_header.tree_hash_root(),
+            "The active header of the best update should be the attested header"
+        );
+
+        let finalized_period =
+            compute_sync_committee_period(self.finalized_beacon_header.header.slot);
+        self.verify_update_slots(update, finalized_period);
+        self.verify_active_header_update(update, finalized_period);
+        self.verify_sync_aggregate(update);
+    }
 
+    fn verify_sync_aggregate(&self, update: &LightClientUpdate) {
         // Verify sync committee has sufficient participants
         let sync_committee_bits =
             BitVec::<u8, Lsb0>::from_slice(&update.sync_aggregate.sync_committee_bits.0);
-        let sync_committee_bits_sum: u64 = sync_committee_bits.count_ones().try_into().unwrap();
+        let sync_committee_bits_sum = get_sync_committee_bits_sum(update);
 
         require!(
             sync_committee_bits_sum >= MIN_SYNC_COMMITTEE_PARTICIPANTS,
@@ -410,14 +1202,64 @@ impl Eth2Client {
             )
         );
 
-        #[cfg(feature = "bls")]
+        #[cfg(any(feature = "bls", feature = "bls_host_functions"))]
         if self.verify_bls_signatures {
+            let finalized_period =
+                compute_sync_committee_period(self.finalized_beacon_header.header.slot);
             self.verify_bls_signatures(update, sync_committee_bits, finalized_period);
         }
     }
 
     fn verify_finality_branch(&self, update: &LightClientUpdate, finalized_period: u64) {
-        // The active header will always be the finalized header because we don't accept updates without the finality update.
+        self.verify_update_slots(update, finalized_period);
+        self.verify_finality_proof(update);
+        self.verify_active_header_update(update, finalized_period);
+    }
+
+    /// Verifies the update of the conflict evidence. Unlike the regular updates, its finalized
+    /// header may be already committed, so only the finality and the signature are verified
+    fn verify_conflict_evidence_update(&self, update: &LightClientUpdate) {
+        require!(
+            update.attested_beacon_header.slot
+                >= update.finality_update.header_update.beacon_header.slot,
+            "The attested header slot should be equal to or higher than the finalized header slot"
+        );
+        require!(
+            update.signature_slot > update.attested_beacon_header.slot,
+            "The signature slot should be higher than the attested header slot"
+        );
+
+        self.verify_finality_proof(update);
+        self.verify_sync_aggregate(update);
+    }
+
+    fn verify_finality_proof(&self, update: &LightClientUpdate) {
+        // Verify that the `finality_branch`, confirms `finalized_header`
+        // to match the finalized checkpoint root saved in the state of `attested_header`.
+        let finalized_root_gindex = self
+            .network_config
+            .finalized_root_gindex_by_slot(update.attested_beacon_header.slot);
+        require!(
+            verify_merkle_proof(
+                H256(
+                    update
+                        .finality_update
+                        .header_update
+                        .beacon_header
+                        .tree_hash_root()
+                ),
+                &update.finality_update.finality_branch,
+                floorlog2(finalized_root_gindex).try_into().unwrap(),
+                get_subtree_index(finalized_root_gindex).try_into().unwrap(),
+                update.attested_beacon_header.state_root
+            ),
+            "Invalid finality proof"
+        );
+    }
+
+    fn verify_update_slots(&self, update: &LightClientUpdate, finalized_period: u64) {
+        // The active header is the finalized header for the regular updates
+        // and the attested header for the best updates
         let active_header = &update.finality_update.header_update.beacon_header;
 
         require!(
@@ -446,26 +1288,13 @@ impl Eth2Client {
                 update_period
             )
         );
+    }
+
+    fn verify_active_header_update(&self, update: &LightClientUpdate, finalized_period: u64) {
+        let config = &self.network_config;
+        let update_period =
+            compute_sync_committee_period(update.finality_update.header_update.beacon_header.slot);
 
-        // Verify that the `finality_branch`, confirms `finalized_header`
-        // to match the finalized checkpoint root saved in the state of `attested_header`.
-        require!(
-            verify_merkle_proof(
-                H256(
-                    update
-                        .finality_update
-                        .header_update
-                        .beacon_header
-                        .tree_hash_root()
-                ),
-                &update.finality_update.finality_branch,
-                FINALITY_TREE_DEPTH.try_into().unwrap(),
-                FINALITY_TREE_INDEX.try_into().unwrap(),
-                update.attested_beacon_header.state_root
-            ),
-            "Invalid finality proof"
-        );
-        let config = NetworkConfig::new(&self.network);
         require!(
             config.validate_beacon_block_header_update(&update.finality_update.header_update),
             "Invalid execution block hash proof"
@@ -478,12 +1307,16 @@ impl Eth2Client {
                 .sync_committee_update
                 .as_ref()
                 .unwrap_or_else(|| env::panic_str("The sync committee update is missed"));
+            let next_sync_committee_gindex =
+                config.next_sync_committee_gindex_by_slot(update.attested_beacon_header.slot);
             require!(
                 verify_merkle_proof(
                     H256(sync_committee_update.next_sync_committee.tree_hash_root()),
                     &sync_committee_update.next_sync_committee_branch,
-                    SYNC_COMMITTEE_TREE_DEPTH.try_into().unwrap(),
-                    SYNC_COMMITTEE_TREE_INDEX.try_into().unwrap(),
+                    floorlog2(next_sync_committee_gindex).try_into().unwrap(),
+                    get_subtree_index(next_sync_committee_gindex)
+                        .try_into()
+                        .unwrap(),
                     update.attested_beacon_header.state_root
                 ),
                 "Invalid next sync committee proof"
@@ -491,14 +1324,14 @@ impl Eth2Client {
         }
     }
 
-    #[cfg(feature = "bls")]
+    #[cfg(any(feature = "bls", feature = "bls_host_functions"))]
     fn verify_bls_signatures(
         &self,
         update: &LightClientUpdate,
         sync_committee_bits: BitVec<u8>,
         finalized_period: u64,
     ) {
-        let config = NetworkConfig::new(&self.network);
+        let config = &self.network_config;
         let signature_period = compute_sync_committee_period(update.signature_slot);
 
         // Verify signature period does not skip a sync committee period
@@ -519,33 +1352,47 @@ impl Eth2Client {
             self.next_sync_committee.get().unwrap()
         };
 
-        let participant_pubkeys =
-            get_participant_pubkeys(&sync_committee.pubkeys.0, &sync_committee_bits);
+        let aggregation_pubkeys =
+            get_pubkeys_for_aggregation(&sync_committee, &sync_committee_bits);
         let fork_version = config
             .compute_fork_version_by_slot(update.signature_slot)
             .unwrap_or_else(|| env::panic_str("Unsupported fork"));
         let domain = compute_domain(
             DOMAIN_SYNC_COMMITTEE,
             fork_version,
-            config.genesis_validators_root.into(),
+            config.genesis_validators_root,
         );
         let signing_root = compute_signing_root(
             eth_types::H256(update.attested_beacon_header.tree_hash_root()),
             domain,
         );
 
-        let aggregate_signature =
-            bls::AggregateSignature::deserialize(&update.sync_aggregate.sync_committee_signature.0)
-                .unwrap();
-        let pubkeys: Vec<bls::PublicKey> = participant_pubkeys
-            .into_iter()
-            .map(|x| bls::PublicKey::deserialize(&x.0).unwrap())
-            .collect();
+        #[cfg(feature = "bls_host_functions")]
         require!(
-            aggregate_signature
-                .fast_aggregate_verify(signing_root.0, &pubkeys.iter().collect::<Vec<_>>()),
+            bls_host_functions::fast_aggregate_verify(
+                &aggregation_pubkeys,
+                signing_root,
+                &update.sync_aggregate.sync_committee_signature
+            ),
             "Failed to verify the bls signature"
         );
+
+        #[cfg(not(feature = "bls_host_functions"))]
+        {
+            let aggregate_signature = bls::AggregateSignature::deserialize(
+                &update.sync_aggregate.sync_committee_signature.0,
+            )
+            .unwrap();
+            let pubkeys: Vec<bls::PublicKey> = aggregation_pubkeys
+                .into_iter()
+                .map(|x| bls::PublicKey::deserialize(&x.0).unwrap())
+                .collect();
+            require!(
+                aggregate_signature
+                    .fast_aggregate_verify(signing_root.0, &pubkeys.iter().collect::<Vec<_>>()),
+                "Failed to verify the bls signature"
+            );
+        }
     }
 
     fn commit_light_client_update(&mut self, update: LightClientUpdate) {
@@ -561,8 +1408,21 @@ impl Eth2Client {
                 .set(&self.next_sync_committee.get().unwrap());
             self.next_sync_committee
                 .set(&update.sync_committee_update.unwrap().next_sync_committee);
+
+            Eth2ClientEvent::SyncCommitteeRotated {
+                period: update_period,
+            }
+            .emit();
         }
 
+        Eth2ClientEvent::LightClientUpdateCommitted {
+            old_finalized_slot: self.finalized_beacon_header.header.slot,
+            new_finalized_slot: finalized_header_update.beacon_header.slot,
+            old_period: finalized_period,
+            new_period: update_period,
+        }
+        .emit();
+
         #[cfg(feature = "logs")]
         env::log_str(
             format!(
@@ -574,34 +1434,72 @@ impl Eth2Client {
         );
 
         self.finalized_beacon_header = finalized_header_update.into();
+        self.best_valid_update.remove();
         self.client_mode = ClientMode::SubmitHeader;
     }
 
-    /// Remove information about the headers that are at least as old as the given block number.
-    /// This method could go out of gas if the client was not synced for a while, to fix that
-    /// you need to increase the `hashes_gc_threshold` by calling `update_hashes_gc_threshold()`
-    fn gc_finalized_execution_blocks(&mut self, mut header_number: u64) {
-        loop {
-            if self
+    /// Remove information about up to `limit` of the oldest headers starting from `gc_cursor`.
+    /// The headers are removed up to the block returned by `get_gc_target_block_number()`.
+    /// Returns the number of processed block numbers
+    fn gc_finalized_execution_blocks(&mut self, limit: u64) -> u64 {
+        let target_block_number = match self.get_gc_target_block_number() {
+            Some(target_block_number) if limit > 0 => target_block_number,
+            _ => return 0,
+        };
+        let start_block_number = self.gc_cursor;
+        if start_block_number > target_block_number {
+            return 0;
+        }
+        let end_block_number =
+            target_block_number.min(start_block_number.saturating_add(limit - 1));
+
+        let mut removed_any = false;
+        for header_number in start_block_number..=end_block_number {
+            removed_any |= self
                 .finalized_execution_blocks
                 .remove(&header_number)
-                .is_some()
-            {
-                if header_number == 0 {
-                    break;
-                } else {
-                    header_number -= 1;
-                }
-            } else {
-                break;
+                .is_some();
+            self.finalized_header_roots.remove(&header_number);
+        }
+        self.gc_cursor = end_block_number + 1;
+
+        // Blocks below the first submitted one are never stored, so the range may be empty
+        if removed_any {
+            Eth2ClientEvent::GcApplied {
+                start_block_number,
+                end_block_number,
             }
+            .emit();
         }
+
+        end_block_number - start_block_number + 1
+    }
+
+    /// Returns the newest block number which should be removed to keep at most
+    /// `hashes_gc_threshold` blocks including the unfinalized ones. The finalized block
+    /// is never removed.
+    fn get_gc_target_block_number(&self) -> Option<u64> {
+        let finalized_block_number = self.finalized_execution_header.get().unwrap().block_number;
+        let diff_between_unfinalized_head_and_tail = self
+            .get_diff_between_unfinalized_head_and_tail()
+            .unwrap_or(0);
+
+        (finalized_block_number + diff_between_unfinalized_head_and_tail)
+            .checked_sub(self.hashes_gc_threshold)
+            .filter(|target| *target > 0)
+            .map(|target| target.min(finalized_block_number.saturating_sub(1)))
     }
 
     fn is_light_client_update_allowed(&self) {
         require!(self.client_mode == ClientMode::SubmitLightClientUpdate);
 
-        if let Some(trusted_signer) = &self.trusted_signer {
+        if self.is_signer_committee_enabled() {
+            require!(
+                self.signer_committee
+                    .contains(&env::predecessor_account_id()),
+                "Eth-client is deployed with the signer committee, only the members can update the client"
+            );
+        } else if let Some(trusted_signer) = &self.trusted_signer {
             require!(
                 &env::predecessor_account_id() == trusted_signer,
                 "Eth-client is deployed as trust mode, only trusted_signer can update the client"
@@ -609,6 +1507,126 @@ impl Eth2Client {
         }
     }
 
+    /// Pauses the submission methods bypassing the pause managers check
+    /// of `pa_pause_feature`, since the conflict evidence is submitted by anyone
+    fn pause_submissions(&mut self) {
+        let mut paused_keys = self.pa_all_paused().unwrap_or_default();
+        paused_keys.extend(
+            PAUSABLE_SUBMISSION_METHODS
+                .iter()
+                .map(|method| method.to_string()),
+        );
+        env::storage_write(self.pa_storage_key(), &paused_keys.try_to_vec().unwrap());
+    }
+
+    /// Stores the change to be applied by `apply_parameter_change` after `parameter_change_delay`
+    /// and returns its id
+    fn stage_parameter_change(&mut self, change: ParameterChange) -> u64 {
+        let id = self.next_parameter_change_id;
+        self.next_parameter_change_id += 1;
+
+        let apply_after = env::block_timestamp() + self.parameter_change_delay;
+        self.pending_parameter_changes.insert(
+            &id,
+            &PendingParameterChange {
+                change: change.clone(),
+                apply_after,
+            },
+        );
+
+        Eth2ClientEvent::ParameterChangeStaged {
+            id,
+            change,
+            apply_after,
+        }
+        .emit();
+        id
+    }
+
+    /// Pays the reward to the predecessor. The reward is reduced to the pool balance
+    /// and to the rest of the epoch cap, so the pool can't be drained within a single epoch
+    fn pay_reward(&mut self, reward: Balance) {
+        let epoch_height = env::epoch_height();
+        if self.rewards_epoch_height != epoch_height {
+            self.rewards_epoch_height = epoch_height;
+            self.rewards_paid_in_epoch = 0;
+        }
+
+        let amount = reward.min(self.reward_pool_balance).min(
+            self.reward_config
+                .max_rewards_per_epoch
+                .0
+                .saturating_sub(self.rewards_paid_in_epoch),
+        );
+        if amount == 0 {
+            return;
+        }
+
+        let relayer = env::predecessor_account_id();
+        self.reward_pool_balance -= amount;
+        self.rewards_paid_in_epoch += amount;
+        self.relayer_payouts.insert(
+            &relayer,
+            &(self.relayer_payouts.get(&relayer).unwrap_or(0) + amount),
+        );
+
+        Eth2ClientEvent::RewardPaid {
+            account_id: relayer.clone(),
+            amount: U128(amount),
+        }
+        .emit();
+        Promise::new(relayer).transfer(amount);
+    }
+
+    fn is_signer_committee_enabled(&self) -> bool {
+        self.signer_committee_threshold > 0
+    }
+
+    fn approve_pending_light_client_update(
+        &mut self,
+        update_hash: H256,
+        mut pending_update: PendingLightClientUpdate,
+    ) {
+        let signer = env::predecessor_account_id();
+        require!(
+            !pending_update.approvals.contains(&signer),
+            "The update is already approved by the signer"
+        );
+        // Each member approves at most one pending update, the previous approval is withdrawn
+        self.withdraw_pending_approval(&signer);
+        pending_update.approvals.push(signer);
+
+        if pending_update.approvals.len() < self.signer_committee_threshold as usize {
+            self.pending_light_client_updates
+                .insert(&update_hash, &pending_update);
+            return;
+        }
+
+        // The other pending updates are outdated after the committed one
+        self.pending_light_client_updates.clear();
+        self.commit_light_client_update(pending_update.update);
+        self.pay_reward(self.reward_config.light_client_update_reward.0);
+    }
+
+    fn withdraw_pending_approval(&mut self, signer: &AccountId) {
+        let approved_update = self
+            .pending_light_client_updates
+            .iter()
+            .find(|(_, pending_update)| pending_update.approvals.contains(signer));
+
+        if let Some((update_hash, mut pending_update)) = approved_update {
+            pending_update
+                .approvals
+                .retain(|approver| approver != signer);
+            if pending_update.approvals.is_empty() {
+                self.pending_light_client_updates.remove(&update_hash);
+            } else {
+                self.pending_light_client_updates
+                    .insert(&update_hash, &pending_update);
+            }
+        }
+    }
+
     fn get_diff_between_unfinalized_head_and_tail(&self) -> Option<u64> {
         let head_block_number = self
             .unfinalized_head_execution_header
@@ -622,3 +1640,10 @@ impl Eth2Client {
         Some(head_block_number - tail_block_number)
     }
 }
+
+fn get_sync_committee_bits_sum(update: &LightClientUpdate) -> u64 {
+    BitVec::<u8, Lsb0>::from_slice(&update.sync_aggregate.sync_committee_bits.0)
+        .count_ones()
+        .try_into()
+        .unwrap()
+}
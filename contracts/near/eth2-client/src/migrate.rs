@@ -1,5 +1,4 @@
 use crate::*;
-use near_sdk::collections::UnorderedMap;
 use near_sdk::Balance;
 
 #[near_bindgen]
@@ -23,12 +22,29 @@ pub struct Eth2ClientV1 {
     next_sync_committee: LazyOption<SyncCommittee>,
 }
 
-#[near_bindgen]
-impl Eth2Client {
-    #[private]
-    #[init(ignore_state)]
-    pub fn migrate() -> Self {
-        let old_state: Eth2ClientV1 = env::state_read().expect("failed");
+/// The state of the contract before the network config became part of the state
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Eth2ClientV2 {
+    trusted_signer: Option<AccountId>,
+    #[deprecated]
+    paused: u128,
+    validate_updates: bool,
+    verify_bls_signatures: bool,
+    hashes_gc_threshold: u64,
+    network: Network,
+    finalized_execution_blocks: LookupMap<u64, H256>,
+    finalized_beacon_header: ExtendedBeaconBlockHeader,
+    finalized_execution_header: LazyOption<ExecutionHeaderInfo>,
+    current_sync_committee: LazyOption<SyncCommittee>,
+    next_sync_committee: LazyOption<SyncCommittee>,
+    client_mode: ClientMode,
+    unfinalized_head_execution_header: Option<ExecutionHeaderInfo>,
+    unfinalized_tail_execution_header: Option<ExecutionHeaderInfo>,
+    trusted_blocks_submitter: Option<AccountId>,
+}
+
+impl From<Eth2ClientV1> for Eth2ClientV2 {
+    fn from(old_state: Eth2ClientV1) -> Self {
         #[allow(deprecated)]
         Self {
             trusted_signer: old_state.trusted_signer,
@@ -49,3 +65,78 @@ impl Eth2Client {
         }
     }
 }
+
+const STATE_KEY: &[u8] = b"STATE";
+
+/// The optional JSON arguments of `migrate`, which can also be called without arguments
+#[derive(Deserialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+struct MigrateArgs {
+    parameter_change_delay: Option<u64>,
+}
+
+#[near_bindgen]
+impl Eth2Client {
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let args: MigrateArgs = env::input()
+            .filter(|input| !input.is_empty())
+            .map(|input| {
+                near_sdk::serde_json::from_slice(&input).expect("Invalid migrate arguments")
+            })
+            .unwrap_or_default();
+        let state = env::storage_read(STATE_KEY).expect("failed");
+        // The v0.1.0 contracts are migrated through the `Eth2ClientV2` layout
+        let old_state = Eth2ClientV2::try_from_slice(&state)
+            .or_else(|_| Eth2ClientV1::try_from_slice(&state).map(Eth2ClientV2::from))
+            .expect("failed");
+        // The blocks older than `hashes_gc_threshold` are already removed by the previous gc
+        let gc_cursor = old_state
+            .finalized_execution_header
+            .get()
+            .unwrap()
+            .block_number
+            .saturating_sub(old_state.hashes_gc_threshold);
+        #[allow(deprecated)]
+        Self {
+            trusted_signer: old_state.trusted_signer,
+            paused: old_state.paused,
+            validate_updates: old_state.validate_updates,
+            verify_bls_signatures: old_state.verify_bls_signatures,
+            hashes_gc_threshold: old_state.hashes_gc_threshold,
+            network_config: NetworkConfig::new(&old_state.network),
+            finalized_execution_blocks: old_state.finalized_execution_blocks,
+            finalized_beacon_header: old_state.finalized_beacon_header,
+            finalized_execution_header: old_state.finalized_execution_header,
+            current_sync_committee: old_state.current_sync_committee,
+            next_sync_committee: old_state.next_sync_committee,
+            client_mode: old_state.client_mode,
+            unfinalized_head_execution_header: old_state.unfinalized_head_execution_header,
+            unfinalized_tail_execution_header: old_state.unfinalized_tail_execution_header,
+            trusted_blocks_submitter: old_state.trusted_blocks_submitter,
+            sparse_mode: false,
+            store_header_roots: false,
+            finalized_header_roots: LookupMap::new(StorageKey::FinalizedHeaderRoots),
+            gc_cursor,
+            best_valid_update: LazyOption::new(StorageKey::BestValidUpdate, None),
+            signer_committee: UnorderedSet::new(StorageKey::SignerCommittee),
+            signer_committee_threshold: 0,
+            pending_light_client_updates: UnorderedMap::new(StorageKey::PendingLightClientUpdates),
+            reward_pool_balance: 0,
+            reward_config: RewardConfig {
+                light_client_update_reward: U128(0),
+                execution_headers_reward: U128(0),
+                max_rewards_per_epoch: U128(0),
+            },
+            rewards_epoch_height: 0,
+            rewards_paid_in_epoch: 0,
+            relayer_payouts: LookupMap::new(StorageKey::RelayerPayouts),
+            parameter_change_delay: args
+                .parameter_change_delay
+                .unwrap_or(DEFAULT_PARAMETER_CHANGE_DELAY),
+            pending_parameter_changes: UnorderedMap::new(StorageKey::PendingParameterChanges),
+            next_parameter_change_id: 0,
+        }
+    }
+}
@@ -1,18 +1,29 @@
 #[cfg(test)]
 mod integration_tests {
     use crate::tests::utils::*;
+    use crate::DEFAULT_PARAMETER_CHANGE_DELAY;
     use borsh::{BorshDeserialize, BorshSerialize};
-    use eth2_utility::types::InitInput;
+    use eth2_utility::types::{ClientMode, InitInput};
     use eth_types::eth2::{ExtendedBeaconBlockHeader, SyncCommittee};
     use eth_types::{Address, Bloom, H256, H64, U256};
-    use near_sdk::ONE_NEAR;
-    use near_units::*;
+    use near_workspaces::operations::Function;
+    use near_workspaces::types::{Gas, NearToken};
+    use near_workspaces::{Account, Contract};
     use serde::{Deserialize, Serialize};
-    use workspaces::operations::Function;
-    use workspaces::{Account, Contract};
 
     const WASM_FILEPATH: &str = "../target/wasm32-unknown-unknown/release/eth2_client.wasm";
     const WASM_V_0_1_0_FILEPATH: &str = "src/data/eth2_client_v0.1.0_testnet.wasm";
+    const WASM_V_0_4_0_FILEPATH: &str = "src/data/eth2_client_v0.4.0_mainnet.wasm";
+    // The update with the BLS signature verification is submitted to the wasm built with the tested backend
+    #[cfg(feature = "bls_host_functions")]
+    const WASM_BLS_FILEPATH: &str =
+        "../target/wasm32-unknown-unknown/release/eth2_client_bls_host_functions.wasm";
+    #[cfg(feature = "bls_host_functions")]
+    const MAX_BLS_VERIFICATION_TGAS: u64 = 100;
+    #[cfg(all(feature = "bls", not(feature = "bls_host_functions")))]
+    const WASM_BLS_FILEPATH: &str = "../target/wasm32-unknown-unknown/release/eth2_client_bls.wasm";
+    #[cfg(all(feature = "bls", not(feature = "bls_host_functions")))]
+    const MAX_BLS_VERIFICATION_TGAS: u64 = 300;
 
     #[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
     pub struct BlockHeaderV1 {
@@ -79,11 +90,51 @@ mod integration_tests {
         }
     }
 
+    #[derive(Clone, BorshDeserialize, BorshSerialize)]
+    pub struct InitInputV2 {
+        pub network: String,
+        pub finalized_execution_header: eth_types::BlockHeader,
+        pub finalized_beacon_header: ExtendedBeaconBlockHeader,
+        pub current_sync_committee: SyncCommittee,
+        pub next_sync_committee: SyncCommittee,
+        pub validate_updates: bool,
+        pub verify_bls_signatures: bool,
+        pub hashes_gc_threshold: u64,
+        pub trusted_signer: Option<near_sdk::AccountId>,
+    }
+
+    impl From<InitInput> for InitInputV2 {
+        fn from(message: InitInput) -> Self {
+            Self {
+                network: message.network,
+                finalized_execution_header: message.finalized_execution_header,
+                finalized_beacon_header: message.finalized_beacon_header,
+                current_sync_committee: message.current_sync_committee,
+                next_sync_committee: message.next_sync_committee,
+                validate_updates: message.validate_updates,
+                verify_bls_signatures: message.verify_bls_signatures,
+                hashes_gc_threshold: message.hashes_gc_threshold,
+                trusted_signer: message.trusted_signer,
+            }
+        }
+    }
+
+    /// Calls the view method with the borsh serialized arguments and result. The contract types
+    /// implement the `borsh` traits of `near-sdk`, which differ from the ones of `near-workspaces`
+    async fn view_borsh<T: BorshDeserialize>(
+        contract: &Contract,
+        method_name: &str,
+        args: Vec<u8>,
+    ) -> anyhow::Result<T> {
+        let result = contract.view(method_name).args(args).await?;
+        Ok(T::try_from_slice(&result.result)?)
+    }
+
     async fn initialize_client<U: borsh::BorshSerialize>(
         init_input: U,
         file_path: &str,
     ) -> anyhow::Result<(Account, Contract)> {
-        let worker = workspaces::sandbox().await?;
+        let worker = near_workspaces::sandbox().await?;
         let wasm = std::fs::read(file_path)?;
         let contract = worker.dev_deploy(&wasm).await?;
 
@@ -91,14 +142,14 @@ mod integration_tests {
         let owner = worker.root_account()?;
         let alice = owner
             .create_subaccount("alice")
-            .initial_balance(parse_near!("30 N"))
+            .initial_balance(NearToken::from_near(30))
             .transact()
             .await?
             .into_result()?;
 
         let result = contract
             .call("init")
-            .args_borsh(init_input)
+            .args(init_input.try_to_vec()?)
             .transact()
             .await?;
 
@@ -124,8 +175,8 @@ mod integration_tests {
             headers.last().unwrap().calculate_hash();
         let outcome = alice
             .call(contract.id(), "submit_beacon_chain_light_client_update")
-            .args_borsh(update)
-            .gas(parse_gas!("300 T") as u64)
+            .args(update.try_to_vec()?)
+            .gas(Gas::from_tgas(300))
             .transact()
             .await?;
         assert!(outcome.is_success());
@@ -136,7 +187,7 @@ mod integration_tests {
                 transaction = transaction.call(
                     Function::new("submit_execution_header")
                         .args(header.try_to_vec()?)
-                        .gas(parse_gas!("6 T") as u64),
+                        .gas(Gas::from_tgas(6)),
                 );
             }
 
@@ -145,16 +196,43 @@ mod integration_tests {
         }
 
         for header in headers {
-            let result: Option<H256> = contract
-                .view("block_hash_safe")
-                .args_borsh(header.number)
-                .await?
-                .borsh()?;
+            let result: Option<H256> =
+                view_borsh(&contract, "block_hash_safe", header.number.try_to_vec()?).await?;
             assert!(result.is_some())
         }
-        println!(
+        println!("Gas burnt: {}", outcome.total_gas_burnt);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg(any(feature = "bls", feature = "bls_host_functions"))]
+    async fn test_gas_usage_of_bls_verification() -> anyhow::Result<()> {
+        let (_headers, updates, init_input) = get_goerli_test_data(Some(InitOptions {
+            validate_updates: true,
+            verify_bls_signatures: true,
+            hashes_gc_threshold: 51000,
+            trusted_signer: None,
+        }));
+        let (alice, contract) = initialize_client(init_input, WASM_BLS_FILEPATH).await?;
+
+        let outcome = alice
+            .call(contract.id(), "submit_beacon_chain_light_client_update")
+            .args(updates[1].try_to_vec()?)
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(outcome.is_success());
+
+        let finalized_slot: u64 =
+            view_borsh(&contract, "finalized_beacon_block_slot", vec![]).await?;
+        assert_eq!(
+            finalized_slot,
+            updates[1].finality_update.header_update.beacon_header.slot
+        );
+        assert!(
+            outcome.total_gas_burnt <= Gas::from_tgas(MAX_BLS_VERIFICATION_TGAS),
             "Gas burnt: {}",
-            gas::to_human(outcome.total_gas_burnt as u128)
+            outcome.total_gas_burnt
         );
         Ok(())
     }
@@ -174,7 +252,7 @@ mod integration_tests {
 
         let result = alice
             .call(contract.id(), "register_submitter")
-            .deposit(20 * ONE_NEAR)
+            .deposit(NearToken::from_near(20))
             .transact()
             .await?;
         assert!(result.is_success());
@@ -187,7 +265,7 @@ mod integration_tests {
                 transaction = transaction.call(
                     Function::new("submit_execution_header")
                         .args(header_v1.try_to_vec()?)
-                        .gas(parse_gas!("6 T") as u64),
+                        .gas(Gas::from_tgas(6)),
                 );
             }
 
@@ -201,19 +279,16 @@ mod integration_tests {
             headers.last().unwrap().calculate_hash();
         let outcome = alice
             .call(contract.id(), "submit_beacon_chain_light_client_update")
-            .args_borsh(update)
-            .gas(parse_gas!("300 T") as u64)
+            .args(update.try_to_vec()?)
+            .gas(Gas::from_tgas(300))
             .transact()
             .await?;
         assert!(outcome.is_success());
 
         // Verify finilized blocks
         for header in &headers {
-            let result: Option<H256> = contract
-                .view("block_hash_safe")
-                .args_borsh(header.number)
-                .await?
-                .borsh()?;
+            let result: Option<H256> =
+                view_borsh(&contract, "block_hash_safe", header.number.try_to_vec()?).await?;
             assert!(result.is_some())
         }
 
@@ -228,18 +303,18 @@ mod integration_tests {
         // Migrate
         let result = contract
             .call("migrate")
-            .gas(parse_gas!("300 T") as u64)
+            .gas(Gas::from_tgas(300))
             .transact()
             .await?;
         assert!(result.is_success());
+        let parameter_change_delay: u64 =
+            contract.view("get_parameter_change_delay").await?.json()?;
+        assert_eq!(parameter_change_delay, DEFAULT_PARAMETER_CHANGE_DELAY);
 
         // Verify finilized blocks after migration
         for header in headers {
-            let result: Option<H256> = contract
-                .view("block_hash_safe")
-                .args_borsh(header.number)
-                .await?
-                .borsh()?;
+            let result: Option<H256> =
+                view_borsh(&contract, "block_hash_safe", header.number.try_to_vec()?).await?;
             assert!(result.is_some())
         }
 
@@ -253,8 +328,8 @@ mod integration_tests {
         // Submit light client update
         let result = alice
             .call(contract.id(), "submit_beacon_chain_light_client_update")
-            .args_borsh(update)
-            .gas(parse_gas!("300 T") as u64)
+            .args(update.try_to_vec()?)
+            .gas(Gas::from_tgas(300))
             .transact()
             .await?;
         assert!(result.is_success());
@@ -266,7 +341,7 @@ mod integration_tests {
                 transaction = transaction.call(
                     Function::new("submit_execution_header")
                         .args(header.try_to_vec()?)
-                        .gas(parse_gas!("6 T") as u64),
+                        .gas(Gas::from_tgas(6)),
                 );
             }
 
@@ -277,14 +352,136 @@ mod integration_tests {
         // Verify finilized blocks [1..num_of_blocks_to_submit*2]
         let headers = headers_data[0].as_slice()[1..num_of_blocks_to_submit * 2].to_vec();
         for header in headers {
-            let result: Option<H256> = contract
-                .view("block_hash_safe")
-                .args_borsh(header.number)
-                .await?
-                .borsh()?;
+            let result: Option<H256> =
+                view_borsh(&contract, "block_hash_safe", header.number.try_to_vec()?).await?;
             assert!(result.is_some())
         }
 
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_migration_from_v0_4_0() -> anyhow::Result<()> {
+        let (headers_data, updates, init_input) = get_goerli_test_data(None);
+        let headers = &headers_data[0];
+        let num_of_blocks_to_submit = 32;
+
+        // The v0.4.0 wasm is built for mainnet, so it runs in the trusted mode with the updates validation
+        let worker = near_workspaces::sandbox().await?;
+        let contract = worker
+            .dev_deploy(&std::fs::read(WASM_V_0_4_0_FILEPATH)?)
+            .await?;
+        let alice = worker
+            .root_account()?
+            .create_subaccount("alice")
+            .initial_balance(NearToken::from_near(30))
+            .transact()
+            .await?
+            .into_result()?;
+        let mut init_input: InitInputV2 = init_input.into();
+        init_input.verify_bls_signatures = false;
+        init_input.trusted_signer = Some(alice.id().as_str().parse()?);
+        let result = contract
+            .call("init")
+            .args(init_input.try_to_vec()?)
+            .transact()
+            .await?;
+        assert!(result.is_success());
+
+        let result = alice
+            .call(contract.id(), "submit_beacon_chain_light_client_update")
+            .args(updates[1].try_to_vec()?)
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(result.is_success());
+
+        // Leave the client in the middle of the execution headers submission
+        let mut transaction = alice.batch(contract.id());
+        for header in headers.iter().rev().take(num_of_blocks_to_submit) {
+            transaction = transaction.call(
+                Function::new("submit_execution_header")
+                    .args(header.try_to_vec()?)
+                    .gas(Gas::from_tgas(6)),
+            );
+        }
+        let result = transaction.transact().await?;
+        assert!(result.is_success());
+
+        // Deploy new version
+        let contract = contract
+            .as_account()
+            .deploy(&(std::fs::read(WASM_FILEPATH).unwrap()))
+            .await
+            .unwrap()
+            .result;
+
+        // Migrate
+        let result = contract
+            .call("migrate")
+            .args_json(serde_json::json!({ "parameter_change_delay": 3600 * 1_000_000_000u64 }))
+            .gas(Gas::from_tgas(300))
+            .transact()
+            .await?;
+        assert!(result.is_success());
+
+        // The state of the execution headers submission is kept
+        let client_mode: ClientMode = view_borsh(&contract, "get_client_mode", vec![]).await?;
+        assert!(client_mode == ClientMode::SubmitHeader);
+        let tail_block_number: Option<u64> =
+            view_borsh(&contract, "get_unfinalized_tail_block_number", vec![]).await?;
+        assert_eq!(
+            tail_block_number,
+            Some(headers[headers.len() - num_of_blocks_to_submit].number)
+        );
+        let trusted_signer: Option<near_sdk::AccountId> =
+            contract.view("get_trusted_signer").await?.json()?;
+        assert_eq!(
+            trusted_signer
+                .as_ref()
+                .map(|account_id| account_id.as_str()),
+            Some(alice.id().as_str())
+        );
+        let finalized_slot: u64 =
+            view_borsh(&contract, "finalized_beacon_block_slot", vec![]).await?;
+        assert_eq!(
+            finalized_slot,
+            updates[1].finality_update.header_update.beacon_header.slot
+        );
+        let parameter_change_delay: u64 =
+            contract.view("get_parameter_change_delay").await?.json()?;
+        assert_eq!(parameter_change_delay, 3600 * 1_000_000_000);
+        let result: Option<H256> = view_borsh(
+            &contract,
+            "block_hash_safe",
+            headers[0].number.try_to_vec()?,
+        )
+        .await?;
+        assert_eq!(result, Some(headers[0].calculate_hash()));
+
+        // Continue the execution headers submission
+        let mut transaction = alice.batch(contract.id());
+        for header in headers
+            .iter()
+            .rev()
+            .skip(num_of_blocks_to_submit)
+            .take(num_of_blocks_to_submit)
+        {
+            transaction = transaction.call(
+                Function::new("submit_execution_header")
+                    .args(header.try_to_vec()?)
+                    .gas(Gas::from_tgas(6)),
+            );
+        }
+        let result = transaction.transact().await?;
+        assert!(result.is_success());
+
+        let tail_block_number: Option<u64> =
+            view_borsh(&contract, "get_unfinalized_tail_block_number", vec![]).await?;
+        assert_eq!(
+            tail_block_number,
+            Some(headers[headers.len() - num_of_blocks_to_submit * 2].number)
+        );
+
         Ok(())
     }
 }
@@ -71,13 +71,32 @@ mod tests {
     #[cfg(not(feature = "mainnet"))]
     mod generic_tests {
         use super::*;
+        use crate::events::Eth2ClientEvent;
+        use crate::{
+            RewardConfig, Role, DEFAULT_PARAMETER_CHANGE_DELAY,
+            MAX_GC_BLOCKS_PER_SPARSE_SUBMISSION, MAX_GC_BLOCKS_PER_SUBMISSION,
+            MAX_LIGHT_CLIENT_UPDATES_PER_SUBMISSION, PAUSABLE_SUBMISSION_METHODS,
+        };
         use bitvec::bitarr;
         use bitvec::order::Lsb0;
         use eth2_utility::consensus::*;
-        use eth_types::{H256, U256};
+        use eth2_utility::types::{ClientMode, InitInput};
+        use eth_types::eth2::{
+            BlobCommitmentProof, BlockRootsProof, HeaderUpdate, HistoricalBlockProof,
+            KzgCommitment, PublicKeyBytes, Validator, KZG_COMMITMENT_BYTES_LEN,
+            PUBLIC_KEY_BYTES_LEN,
+        };
+        #[cfg(feature = "bls")]
+        use eth_types::eth2::{
+            SignatureBytes, SyncAggregate, SyncCommittee, SyncCommitteeBits,
+            SyncCommitteePublicKeys, SYNC_COMMITTEE_BITS_SIZE_IN_BYTES,
+        };
+        use eth_types::events::NearEvent;
+        use eth_types::{BlockHeaderRoots, H256, U256};
         use hex::FromHex;
-        use near_plugins::Pausable;
-        use near_sdk::test_utils::accounts;
+        use near_plugins::{AccessControllable, Pausable};
+        use near_sdk::json_types::U128;
+        use near_sdk::test_utils::{accounts, get_logs};
         use tree_hash::TreeHash;
 
         #[test]
@@ -133,6 +152,343 @@ mod tests {
             );
         }
 
+        #[test]
+        pub fn test_submit_updates_batch() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: submitter);
+
+            contract.submit_beacon_chain_light_client_updates(updates[1..3].to_vec());
+            assert_eq!(
+                contract.finalized_beacon_block_slot(),
+                updates[2].finality_update.header_update.beacon_header.slot
+            );
+            assert!(contract.get_client_mode() == ClientMode::SubmitHeader);
+
+            let headers: Vec<&BlockHeader> =
+                headers[0].iter().skip(1).chain(headers[1].iter()).collect();
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers.iter().rev().cloned().collect(),
+            );
+
+            assert_eq!(contract.last_block_number(), headers.last().unwrap().number);
+        }
+
+        #[test]
+        #[should_panic(expected = "The list of updates is empty")]
+        pub fn test_panic_on_submit_empty_updates_batch() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates: _,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_beacon_chain_light_client_updates(vec![]);
+        }
+
+        #[test]
+        #[should_panic(expected = "The list of updates is too long")]
+        pub fn test_panic_on_submit_too_long_updates_batch() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_beacon_chain_light_client_updates(vec![
+                updates[1].clone();
+                MAX_LIGHT_CLIENT_UPDATES_PER_SUBMISSION
+                    + 1
+            ]);
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "The active header of the best update should be the attested header"
+        )]
+        pub fn test_panic_on_submit_finalized_update_as_best_update() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_beacon_chain_best_light_client_update(updates[1].clone());
+        }
+
+        #[test]
+        #[should_panic(expected = "The best valid update is missing")]
+        pub fn test_panic_on_force_update_without_best_update() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates: _,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            assert!(contract.get_best_valid_update().is_none());
+            contract.force_light_client_update();
+        }
+
+        fn get_best_update_test_context() -> (TestContext<'static>, LightClientUpdate) {
+            let test_context = get_test_context(Some(InitOptions {
+                validate_updates: false,
+                verify_bls_signatures: false,
+                hashes_gc_threshold: 51000,
+                trusted_signer: None,
+            }));
+            // The best update has the attested header as the active header
+            let mut best_update = test_context.updates[1].clone();
+            best_update.attested_beacon_header = best_update
+                .finality_update
+                .header_update
+                .beacon_header
+                .clone();
+            (test_context, best_update)
+        }
+
+        fn check_forced_update(contract: &Eth2Client, best_update: &LightClientUpdate) {
+            let expected_event = Eth2ClientEvent::LightClientUpdateForced {
+                attested_slot: best_update.attested_beacon_header.slot,
+                signature_slot: best_update.signature_slot,
+            };
+            assert!(get_logs().contains(&format!("EVENT_JSON:{}", expected_event.to_json_string())));
+            assert_eq!(
+                contract.finalized_beacon_block_slot(),
+                best_update.attested_beacon_header.slot
+            );
+            assert!(contract.get_best_valid_update().is_none());
+            assert!(contract.get_client_mode() == ClientMode::SubmitHeader);
+        }
+
+        #[test]
+        pub fn test_force_update_after_timeout() {
+            let (TestContext { mut contract, .. }, mut best_update) =
+                get_best_update_test_context();
+            best_update.signature_slot =
+                contract.finalized_beacon_block_slot() + UPDATE_TIMEOUT + 1;
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_beacon_chain_best_light_client_update(best_update.clone());
+            assert_eq!(
+                contract.get_best_valid_update().unwrap().signature_slot,
+                best_update.signature_slot
+            );
+
+            contract.force_light_client_update();
+            check_forced_update(&contract, &best_update);
+        }
+
+        #[test]
+        pub fn test_force_update_by_dao() {
+            let (TestContext { mut contract, .. }, best_update) = get_best_update_test_context();
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            contract.acl_grant_role(Role::DAO.into(), accounts(1));
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+            contract.submit_beacon_chain_best_light_client_update(best_update.clone());
+
+            // The timeout has not passed, but the DAO can apply the best update at any time
+            assert!(
+                best_update.signature_slot
+                    <= contract.finalized_beacon_block_slot() + UPDATE_TIMEOUT
+            );
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(1));
+            contract.force_light_client_update();
+            check_forced_update(&contract, &best_update);
+        }
+
+        #[test]
+        pub fn test_submit_update_in_sparse_mode() {
+            let (headers, updates, mut init_input) = get_test_data(None);
+            init_input.sparse_mode = true;
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init(init_input);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let finalized_header = headers[0].last().unwrap();
+            contract.submit_execution_header(finalized_header.clone());
+
+            assert!(contract.get_client_mode() == ClientMode::SubmitLightClientUpdate);
+            assert_eq!(contract.last_block_number(), finalized_header.number);
+            assert_eq!(
+                contract.block_hash_safe(finalized_header.number),
+                Some(finalized_header.calculate_hash())
+            );
+            assert_eq!(contract.block_hash_safe(finalized_header.number - 1), None);
+        }
+
+        #[test]
+        pub fn test_gc_in_sparse_mode() {
+            let (headers, updates, mut init_input) = get_test_data(Some(InitOptions {
+                validate_updates: true,
+                verify_bls_signatures: VERIFY_BLS_SIGNATURES,
+                hashes_gc_threshold: 500,
+                trusted_signer: None,
+            }));
+            init_input.sparse_mode = true;
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init(init_input);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let finalized_header = headers[0].last().unwrap();
+            contract.submit_execution_header(finalized_header.clone());
+
+            let expected_event = Eth2ClientEvent::GcApplied {
+                start_block_number: headers[0][0].number,
+                end_block_number: headers[0][0].number + MAX_GC_BLOCKS_PER_SPARSE_SUBMISSION - 1,
+            };
+            let expected_log = format!("EVENT_JSON:{}", expected_event.to_json_string());
+            assert!(get_logs().contains(&expected_log));
+            assert!(!contract.is_known_execution_header(headers[0][0].number));
+            assert!(contract.is_known_execution_header(finalized_header.number));
+
+            // The intermediate blocks are never stored in the sparse mode,
+            // so the next gc range has nothing to remove
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+            contract.submit_beacon_chain_light_client_update(updates[2].clone());
+            contract.submit_execution_header(headers[1].last().unwrap().clone());
+            assert!(!get_logs()
+                .iter()
+                .any(|log| log.contains("\"event\":\"gc_applied\"")));
+        }
+
+        #[test]
+        pub fn test_gc_backlog_in_sparse_mode() {
+            let (headers, updates, mut init_input) = get_test_data(Some(InitOptions {
+                validate_updates: false,
+                verify_bls_signatures: false,
+                hashes_gc_threshold: 500,
+                trusted_signer: None,
+            }));
+            init_input.sparse_mode = true;
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init(init_input);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            contract.submit_execution_header(headers[0].last().unwrap().clone());
+            let initial_gc_backlog = contract.get_gc_backlog();
+
+            // The checkpoints are submitted 3 epochs apart, so the finalized block moves
+            // by more than `MAX_GC_BLOCKS_PER_SUBMISSION` blocks on each submission
+            let mut update = updates[1].clone();
+            let mut header = headers[0].last().unwrap().clone();
+            for _ in 0..10 {
+                header.number += 3 * MAX_GC_BLOCKS_PER_SUBMISSION;
+                update.finality_update.header_update.beacon_header.slot += 3 * SLOTS_PER_EPOCH;
+                update.finality_update.header_update.execution_block_hash = header.calculate_hash();
+
+                set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+                contract.submit_beacon_chain_light_client_update(update.clone());
+                contract.submit_execution_header(header.clone());
+                assert_eq!(contract.last_block_number(), header.number);
+                assert!(contract.get_gc_backlog() <= initial_gc_backlog);
+            }
+        }
+
+        #[test]
+        pub fn test_events() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: submitter);
+
+            let old_finalized_slot = contract.finalized_beacon_block_slot();
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let new_finalized_slot = contract.finalized_beacon_block_slot();
+            let expected_event = Eth2ClientEvent::LightClientUpdateCommitted {
+                old_finalized_slot,
+                new_finalized_slot,
+                old_period: compute_sync_committee_period(old_finalized_slot),
+                new_period: compute_sync_committee_period(new_finalized_slot),
+            };
+            let expected_log = format!("EVENT_JSON:{}", expected_event.to_json_string());
+            assert!(get_logs().contains(&expected_log));
+
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers[0].iter().skip(1).rev().collect(),
+            );
+            let expected_event = Eth2ClientEvent::ExecutionHeadersFinalized {
+                start_block_number: headers[0][1].number,
+                end_block_number: headers[0].last().unwrap().number,
+            };
+            let expected_log = format!("EVENT_JSON:{}", expected_event.to_json_string());
+            assert!(get_logs().contains(&expected_log));
+        }
+
+        #[test]
+        pub fn test_store_header_roots() {
+            let (headers, updates, mut init_input) = get_test_data(None);
+            init_input.store_header_roots = true;
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init(init_input);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            // The roots of the unfinalized blocks are not available
+            let last_header = headers[0].last().unwrap();
+            assert_eq!(contract.header_roots_safe(last_header.number), None);
+
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers[0].iter().skip(1).rev().collect(),
+            );
+
+            for header in headers[0].iter() {
+                assert_eq!(
+                    contract.header_roots_safe(header.number),
+                    Some(BlockHeaderRoots::from(header))
+                );
+            }
+
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            contract.update_store_header_roots(false);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_beacon_chain_light_client_update(updates[2].clone());
+            submit_and_check_execution_headers(&mut contract, headers[1].iter().rev().collect());
+
+            assert_eq!(
+                contract.last_block_number(),
+                headers[1].last().unwrap().number
+            );
+            for header in headers[1].iter() {
+                assert_eq!(contract.header_roots_safe(header.number), None);
+            }
+        }
+
         #[test]
         #[should_panic(expected = "The expected block hash is")]
         pub fn test_panic_on_submit_execution_block_from_fork_chain() {
@@ -162,7 +518,7 @@ mod tests {
                 updates,
             } = get_test_context(Some(InitOptions {
                 validate_updates: true,
-                verify_bls_signatures: true,
+                verify_bls_signatures: VERIFY_BLS_SIGNATURES,
                 hashes_gc_threshold: hashes_gc_threshold.try_into().unwrap(),
                 trusted_signer: None,
             }));
@@ -213,6 +569,437 @@ mod tests {
             }
         }
 
+        #[test]
+        pub fn test_gc_with_limit() {
+            let submitter = accounts(0);
+            let hashes_gc_threshold = 500;
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(Some(InitOptions {
+                validate_updates: true,
+                verify_bls_signatures: VERIFY_BLS_SIGNATURES,
+                hashes_gc_threshold,
+                trusted_signer: None,
+            }));
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: submitter);
+
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            // The unfinalized blocks exceed the `hashes_gc_threshold`, the gc is postponed
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers[0].iter().skip(1).rev().collect(),
+            );
+
+            let first_block_number = headers[0][0].number;
+            let gc_target = headers[0].last().unwrap().number - hashes_gc_threshold;
+            assert_eq!(
+                contract.get_gc_backlog(),
+                gc_target - first_block_number + 1
+            );
+
+            assert_eq!(contract.gc(100), 100);
+            assert_eq!(
+                contract.get_gc_backlog(),
+                gc_target - first_block_number - 99
+            );
+            assert!(contract.block_hash_safe(first_block_number + 99).is_none());
+            assert!(contract.block_hash_safe(first_block_number + 100).is_some());
+
+            contract.gc(u64::MAX);
+            assert_eq!(contract.get_gc_backlog(), 0);
+            assert!(contract.block_hash_safe(gc_target).is_none());
+            assert!(contract.block_hash_safe(gc_target + 1).is_some());
+            assert_eq!(contract.gc(100), 0);
+        }
+
+        #[test]
+        pub fn test_signer_committee() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            contract.add_signer_committee_member(accounts(1));
+            contract.add_signer_committee_member(accounts(2));
+            contract.add_signer_committee_member(accounts(3));
+            contract.update_signer_committee_threshold(2);
+            contract.update_reward_config(RewardConfig {
+                light_client_update_reward: U128(100),
+                execution_headers_reward: U128(50),
+                max_rewards_per_epoch: U128(1000),
+            });
+            set_env!(attached_deposit: 1000u128, predecessor_account_id: accounts(0));
+            contract.deposit_reward_pool();
+
+            let finalized_slot = contract.finalized_beacon_block_slot();
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(1));
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            assert_eq!(contract.finalized_beacon_block_slot(), finalized_slot);
+
+            let pending_updates = contract.get_pending_light_client_updates();
+            assert_eq!(pending_updates.len(), 1);
+            let (update_hash, approvals) = pending_updates[0].clone();
+            assert_eq!(approvals, vec![accounts(1)]);
+            assert!(contract
+                .get_pending_light_client_update(update_hash)
+                .is_some());
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(3));
+            contract.approve_light_client_update(update_hash);
+            assert_eq!(
+                contract.finalized_beacon_block_slot(),
+                updates[1].finality_update.header_update.beacon_header.slot
+            );
+            assert!(contract.get_pending_light_client_updates().is_empty());
+
+            // The reward is paid to the member whose approval committed the update
+            assert_eq!(contract.get_relayer_payouts(accounts(1)), U128(0));
+            assert_eq!(contract.get_relayer_payouts(accounts(3)), U128(100));
+        }
+
+        #[test]
+        #[should_panic(expected = "The update is already approved by the signer")]
+        pub fn test_panic_on_signer_committee_double_approval() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            contract.add_signer_committee_member(accounts(1));
+            contract.add_signer_committee_member(accounts(2));
+            contract.update_signer_committee_threshold(2);
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(1));
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+        }
+
+        #[test]
+        pub fn test_signer_committee_member_approves_single_update() {
+            let (
+                TestContext {
+                    mut contract,
+                    updates,
+                    ..
+                },
+                _,
+            ) = get_best_update_test_context();
+            contract.add_signer_committee_member(accounts(1));
+            contract.add_signer_committee_member(accounts(2));
+            contract.add_signer_committee_member(accounts(3));
+            contract.update_signer_committee_threshold(2);
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(1));
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            let (first_update_hash, _) = contract.get_pending_light_client_updates()[0].clone();
+
+            // The new submission of the member withdraws the approval of the first update
+            let mut second_update = updates[1].clone();
+            second_update.signature_slot += 1;
+            contract.submit_beacon_chain_light_client_update(second_update);
+            let pending_updates = contract.get_pending_light_client_updates();
+            assert_eq!(pending_updates.len(), 1);
+            assert_ne!(pending_updates[0].0, first_update_hash);
+            assert_eq!(pending_updates[0].1, vec![accounts(1)]);
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(2));
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            assert_eq!(contract.get_pending_light_client_updates().len(), 2);
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(3));
+            contract.approve_light_client_update(first_update_hash);
+            assert_eq!(
+                contract.finalized_beacon_block_slot(),
+                updates[1].finality_update.header_update.beacon_header.slot
+            );
+            assert!(contract.get_pending_light_client_updates().is_empty());
+        }
+
+        #[test]
+        pub fn test_signer_committee_discards_best_valid_update() {
+            let (TestContext { mut contract, .. }, best_update) = get_best_update_test_context();
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+            contract.submit_beacon_chain_best_light_client_update(best_update);
+            assert!(contract.get_best_valid_update().is_some());
+
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            contract.add_signer_committee_member(accounts(1));
+            contract.update_signer_committee_threshold(1);
+            assert!(contract.get_best_valid_update().is_none());
+        }
+
+        #[test]
+        #[should_panic(expected = "The forced update is not supported with the signer committee")]
+        pub fn test_panic_on_force_update_with_signer_committee() {
+            let (TestContext { mut contract, .. }, _) = get_best_update_test_context();
+            contract.acl_grant_role(Role::DAO.into(), accounts(1));
+            contract.add_signer_committee_member(accounts(1));
+            contract.update_signer_committee_threshold(1);
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(1));
+            contract.force_light_client_update();
+        }
+
+        #[test]
+        #[should_panic(expected = "only the members can update the client")]
+        pub fn test_panic_on_submit_update_by_non_committee_member() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            contract.add_signer_committee_member(accounts(1));
+            contract.update_signer_committee_threshold(1);
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+        }
+
+        #[test]
+        #[should_panic(expected = "can't be verified without the BLS signatures verification")]
+        pub fn test_panic_on_conflict_evidence_without_bls_verification() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(Some(InitOptions {
+                validate_updates: true,
+                verify_bls_signatures: false,
+                hashes_gc_threshold: 7100,
+                trusted_signer: None,
+            }));
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_conflict_evidence(updates[1].clone(), updates[2].clone());
+        }
+
+        #[test]
+        #[cfg_attr(not(feature = "bls"), ignore)]
+        #[should_panic(expected = "The finalized headers of the evidence are not conflicting")]
+        pub fn test_panic_on_not_conflicting_evidence() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates,
+            } = get_test_context(None);
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+
+            contract.submit_conflict_evidence(updates[1].clone(), updates[1].clone());
+        }
+
+        #[test]
+        pub fn test_pausable_submission_methods() {
+            // Each method with the `#[pause]` attribute is paused on the conflict evidence
+            let source = include_str!("../lib.rs");
+            let mut lines = source.lines();
+            let mut pausable_methods = vec![];
+            while let Some(line) = lines.next() {
+                if line.trim_start().starts_with("#[pause(") {
+                    let method = lines
+                        .find_map(|line| line.trim_start().strip_prefix("pub fn "))
+                        .and_then(|line| line.split('(').next())
+                        .unwrap();
+                    pausable_methods.push(method);
+                }
+            }
+
+            pausable_methods.sort_unstable();
+            let mut expected_methods = PAUSABLE_SUBMISSION_METHODS.to_vec();
+            expected_methods.sort_unstable();
+            assert_eq!(pausable_methods, expected_methods);
+        }
+
+        #[cfg(feature = "bls")]
+        fn sign_update(
+            update: &mut LightClientUpdate,
+            secret_key: &bls::SecretKey,
+            network_config: &NetworkConfig,
+        ) {
+            let fork_version = network_config
+                .compute_fork_version_by_slot(update.signature_slot)
+                .unwrap();
+            let domain = compute_domain(
+                DOMAIN_SYNC_COMMITTEE,
+                fork_version,
+                network_config.genesis_validators_root,
+            );
+            let signing_root =
+                compute_signing_root(H256(update.attested_beacon_header.tree_hash_root()), domain);
+
+            // All members of the committee share the same key
+            let signature = secret_key.sign(signing_root.0);
+            let mut aggregate_signature = bls::AggregateSignature::infinity();
+            for _ in 0..SYNC_COMMITTEE_BITS_SIZE_IN_BYTES * 8 {
+                aggregate_signature.add_assign(&signature);
+            }
+            update.sync_aggregate = SyncAggregate {
+                sync_committee_bits: SyncCommitteeBits(
+                    [u8::MAX; SYNC_COMMITTEE_BITS_SIZE_IN_BYTES],
+                ),
+                sync_committee_signature: SignatureBytes(aggregate_signature.serialize()),
+            };
+        }
+
+        #[cfg(feature = "bls")]
+        #[test]
+        pub fn test_conflict_evidence_pauses_submissions() {
+            let (_headers, updates, mut init_input) = get_test_data(None);
+            // The real sync committee never signed conflicting headers, so the evidence
+            // is signed by the committee of the test key
+            let secret_key = bls::SecretKey::deserialize(&[1u8; 32]).unwrap();
+            let public_key = PublicKeyBytes(secret_key.public_key().compress().serialize());
+            let sync_committee = SyncCommittee {
+                pubkeys: SyncCommitteePublicKeys(vec![
+                    public_key.clone();
+                    SYNC_COMMITTEE_BITS_SIZE_IN_BYTES * 8
+                ]),
+                aggregate_pubkey: public_key,
+            };
+            init_input.current_sync_committee = sync_committee.clone();
+            init_input.next_sync_committee = sync_committee;
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init(init_input);
+
+            let evidence: Vec<LightClientUpdate> = [0, 1]
+                .into_iter()
+                .map(|proposer_index| {
+                    let mut update = updates[1].clone();
+                    let finalized_header = &mut update.finality_update.header_update.beacon_header;
+                    finalized_header.proposer_index = proposer_index;
+                    let finalized_root_gindex = contract
+                        .network_config
+                        .finalized_root_gindex_by_slot(update.attested_beacon_header.slot);
+                    let (state_root, finality_branch) = build_merkle_proof(
+                        H256(finalized_header.tree_hash_root()),
+                        finalized_root_gindex.into(),
+                    );
+                    update.attested_beacon_header.state_root = state_root;
+                    update.finality_update.finality_branch = finality_branch;
+                    sign_update(&mut update, &secret_key, &contract.network_config);
+                    update
+                })
+                .collect();
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+            contract.submit_conflict_evidence(evidence[0].clone(), evidence[1].clone());
+
+            for method in PAUSABLE_SUBMISSION_METHODS {
+                assert!(contract.pa_is_paused(method.to_string()));
+            }
+            assert!(get_logs()
+                .iter()
+                .any(|log| log.contains("\"event\":\"conflicting_finality_detected\"")));
+        }
+
+        #[test]
+        pub fn test_reward_pool() {
+            let submitter = accounts(0);
+            let TestContext {
+                mut contract,
+                headers,
+                updates,
+            } = get_test_context(None);
+            contract.update_reward_config(RewardConfig {
+                light_client_update_reward: U128(100),
+                execution_headers_reward: U128(50),
+                max_rewards_per_epoch: U128(120),
+            });
+            set_env!(attached_deposit: 1000u128, predecessor_account_id: accounts(2));
+            contract.deposit_reward_pool();
+            assert_eq!(contract.get_reward_pool_balance(), U128(1000));
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: submitter.clone());
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+            assert_eq!(contract.get_relayer_payouts(submitter.clone()), U128(100));
+
+            // The headers reward is limited by the epoch cap
+            submit_and_check_execution_headers(
+                &mut contract,
+                headers[0].iter().skip(1).rev().collect(),
+            );
+            assert_eq!(contract.get_relayer_payouts(submitter.clone()), U128(120));
+            assert_eq!(contract.get_rewards_paid_in_epoch(), U128(120));
+            assert_eq!(contract.get_reward_pool_balance(), U128(880));
+
+            set_env!(
+                prepaid_gas: 10u64.pow(18),
+                predecessor_account_id: submitter.clone(),
+                epoch_height: 1u64
+            );
+            assert_eq!(contract.get_rewards_paid_in_epoch(), U128(0));
+            contract.submit_beacon_chain_light_client_update(updates[2].clone());
+            assert_eq!(contract.get_relayer_payouts(submitter), U128(220));
+            assert_eq!(contract.get_reward_pool_balance(), U128(780));
+        }
+
+        #[test]
+        pub fn test_parameter_change_delay() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates: _,
+            } = get_test_context(None);
+            let id = contract.update_parameter_change_delay(1000);
+            contract.apply_parameter_change(id);
+            assert_eq!(contract.get_parameter_change_delay(), 1000);
+
+            let id = contract.update_trusted_signer(Some(accounts(1)));
+            let cancelled_id = contract.update_hashes_gc_threshold(100);
+            assert_eq!(contract.get_pending_parameter_changes().len(), 2);
+            contract.cancel_parameter_change(cancelled_id);
+            assert_eq!(contract.get_pending_parameter_changes().len(), 1);
+
+            set_env!(
+                predecessor_account_id: eth2_client_account(),
+                block_timestamp: 1000u64
+            );
+            contract.apply_parameter_change(id);
+            assert_eq!(contract.get_trusted_signer(), Some(accounts(1)));
+            assert!(contract.get_pending_parameter_changes().is_empty());
+        }
+
+        #[test]
+        pub fn test_default_parameter_change_delay() {
+            let (_, _, mut init_input) = get_test_data(None);
+            init_input.parameter_change_delay = None;
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let contract = Eth2Client::init(init_input);
+            assert_eq!(
+                contract.get_parameter_change_delay(),
+                DEFAULT_PARAMETER_CHANGE_DELAY
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "The parameter change delay hasn't passed yet")]
+        pub fn test_panic_on_parameter_change_before_delay() {
+            let TestContext {
+                mut contract,
+                headers: _,
+                updates: _,
+            } = get_test_context(None);
+            let id = contract.update_parameter_change_delay(1000);
+            contract.apply_parameter_change(id);
+
+            let id = contract.update_trusted_signer(Some(accounts(1)));
+            set_env!(
+                predecessor_account_id: eth2_client_account(),
+                block_timestamp: 999u64
+            );
+            contract.apply_parameter_change(id);
+        }
+
         #[test]
         #[should_panic(expected = "only trusted_signer can update the client")]
         pub fn test_trusted_signer() {
@@ -223,7 +1010,7 @@ mod tests {
                 updates,
             } = get_test_context(Some(InitOptions {
                 validate_updates: true,
-                verify_bls_signatures: true,
+                verify_bls_signatures: VERIFY_BLS_SIGNATURES,
                 hashes_gc_threshold: 7100,
                 trusted_signer: Some(trusted_signer),
             }));
@@ -457,6 +1244,433 @@ mod tests {
             update.sync_committee_update = None;
             contract.submit_beacon_chain_light_client_update(update);
         }
+
+        #[test]
+        pub fn test_init_with_network_config() {
+            let (_headers, updates, mut init_input) = get_test_data(None);
+            let network_config = NetworkConfig::new(&Network::Goerli);
+            init_input.network = "devnet".to_string();
+            init_input.network_config = Some(network_config.clone());
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            let mut contract = Eth2Client::init(init_input);
+            assert_eq!(contract.get_network_config(), network_config);
+
+            set_env!(prepaid_gas: 10u64.pow(18), predecessor_account_id: accounts(0));
+            contract.submit_beacon_chain_light_client_update(updates[1].clone());
+        }
+
+        #[test]
+        #[should_panic(expected = "The fork schedule is empty")]
+        pub fn test_panic_on_init_with_empty_fork_schedule() {
+            let (_headers, _updates, mut init_input) = get_test_data(None);
+            init_input.network_config = Some(NetworkConfig {
+                genesis_validators_root: H256::from([0; 32]),
+                forks: vec![],
+            });
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            Eth2Client::init(init_input);
+        }
+
+        /// Returns the Goerli config with `fork` active from genesis, so any header
+        /// of the test data can be proven against the beacon state layout of the fork
+        fn get_network_config_since(fork: Fork) -> NetworkConfig {
+            let mut network_config = NetworkConfig::new(&Network::Goerli);
+            network_config
+                .forks
+                .retain(|fork_config| fork_config.fork >= fork);
+            network_config.forks[0].epoch = 0;
+            network_config
+        }
+
+        /// Initializes the client with the finalized beacon state `state_root` at `state_slot`.
+        /// The test data doesn't include the beacon states, so the state proofs are built
+        /// against the given root
+        fn init_with_finalized_state(
+            mut init_input: InitInput,
+            network_config: NetworkConfig,
+            state_slot: u64,
+            state_root: H256,
+        ) -> Eth2Client {
+            let finalized_beacon_header = &mut init_input.finalized_beacon_header;
+            finalized_beacon_header.header.slot = state_slot;
+            finalized_beacon_header.header.state_root = state_root;
+            finalized_beacon_header.beacon_block_root =
+                H256(finalized_beacon_header.header.tree_hash_root());
+            init_input.network_config = Some(network_config);
+
+            set_env!(
+                current_account_id: eth2_client_account(),
+                predecessor_account_id: eth2_client_account(),
+            );
+            Eth2Client::init(init_input)
+        }
+
+        /// Returns the root of the tree with `leaf` at `gindex` and the branch of the leaf
+        fn build_merkle_proof(leaf: H256, gindex: u64) -> (H256, Vec<H256>) {
+            let depth = 63 - gindex.leading_zeros() as usize;
+            let index = (gindex - (1 << depth)) as usize;
+            let branch: Vec<H256> = (0..depth).map(|i| H256::from([i as u8; 32])).collect();
+            (merkle_root_from_branch(leaf, &branch, depth, index), branch)
+        }
+
+        /// Returns the root of the tree with the leaves at the given generalized indices and
+        /// the branch of each leaf. The nodes off the paths of the leaves are arbitrary
+        fn build_merkle_multiproof(leaves: &[(u64, H256)]) -> (H256, Vec<Vec<H256>>) {
+            fn depth(gindex: u64) -> u32 {
+                63 - gindex.leading_zeros()
+            }
+            fn node(gindex: u64, leaves: &[(u64, H256)]) -> H256 {
+                if let Some((_, leaf)) = leaves
+                    .iter()
+                    .find(|(leaf_gindex, _)| *leaf_gindex == gindex)
+                {
+                    return *leaf;
+                }
+                let is_ancestor = leaves.iter().any(|(leaf_gindex, _)| {
+                    depth(*leaf_gindex) > depth(gindex)
+                        && leaf_gindex >> (depth(*leaf_gindex) - depth(gindex)) == gindex
+                });
+                if !is_ancestor {
+                    return H256::from([depth(gindex) as u8; 32]);
+                }
+                merkle_root_from_branch(
+                    node(gindex * 2, leaves),
+                    &[node(gindex * 2 + 1, leaves)],
+                    1,
+                    0,
+                )
+            }
+
+            let branches = leaves
+                .iter()
+                .map(|(leaf_gindex, _)| {
+                    let mut gindex = *leaf_gindex;
+                    let mut branch = vec![];
+                    while gindex > 1 {
+                        branch.push(node(gindex ^ 1, leaves));
+                        gindex /= 2;
+                    }
+                    branch
+                })
+                .collect();
+            (node(1, leaves), branches)
+        }
+
+        /// Returns the Deneb header update with the blob commitment at `blob_index` and the
+        /// branch of the commitment in the block body
+        fn get_blob_header_update(
+            header_update: &HeaderUpdate,
+            network_config: &NetworkConfig,
+            commitment: &KzgCommitment,
+            blob_index: u64,
+        ) -> (HeaderUpdate, Vec<H256>) {
+            let proof_size =
+                network_config.compute_proof_size_by_slot(header_update.beacon_header.slot);
+            let execution_payload_gindex = (1 << proof_size.beacon_block_body_tree_depth)
+                | proof_size.l1_beacon_block_body_tree_execution_payload_index as u64;
+            let execution_block_hash_gindex = (execution_payload_gindex
+                << proof_size.l2_execution_payload_proof_size)
+                | proof_size.l2_execution_payload_tree_execution_block_index as u64;
+            // `blob_kzg_commitments[blob_index]`, the list length mixin and the field of the body
+            let commitment_gindex = ((BLOB_KZG_COMMITMENTS_INDEX as u64 * 2)
+                << floorlog2(MAX_BLOB_COMMITMENTS_PER_BLOCK as u32))
+                | blob_index;
+
+            let (body_root, mut branches) = build_merkle_multiproof(&[
+                (
+                    execution_block_hash_gindex,
+                    header_update.execution_block_hash,
+                ),
+                (commitment_gindex, H256(commitment.tree_hash_root())),
+            ]);
+            let mut blob_header_update = header_update.clone();
+            blob_header_update.beacon_header.body_root = body_root;
+            blob_header_update.execution_hash_branch = branches.remove(0);
+            (blob_header_update, branches.remove(0))
+        }
+
+        #[test]
+        pub fn test_recent_block_hash_safe() {
+            let (_headers, updates, init_input) = get_test_data(None);
+            let network_config = get_network_config_since(Fork::Capella);
+            let header_update = updates[0].finality_update.header_update.clone();
+            let slot = header_update.beacon_header.slot;
+            let state_slot = slot + 100;
+
+            let block_roots_gindex = network_config.block_roots_gindex_by_slot(state_slot) as u64;
+            let (state_root, block_roots_branch) = build_merkle_proof(
+                H256(header_update.beacon_header.tree_hash_root()),
+                (block_roots_gindex << BLOCK_ROOTS_TREE_DEPTH) | (slot % SLOTS_PER_HISTORICAL_ROOT),
+            );
+            let contract = init_with_finalized_state(
+                init_input.clone(),
+                network_config.clone(),
+                state_slot,
+                state_root,
+            );
+
+            let proof = BlockRootsProof {
+                header_update: header_update.clone(),
+                block_roots_branch,
+            };
+            assert_eq!(
+                contract.recent_block_hash_safe(proof.clone()),
+                Some(header_update.execution_block_hash)
+            );
+
+            let mut invalid_proof = proof.clone();
+            invalid_proof.header_update.execution_block_hash = H256::from([0x01; 32]);
+            assert_eq!(contract.recent_block_hash_safe(invalid_proof), None);
+
+            let mut invalid_proof = proof.clone();
+            invalid_proof.block_roots_branch[0] = H256::from([0x01; 32]);
+            assert_eq!(contract.recent_block_hash_safe(invalid_proof), None);
+
+            // The root of the block is out of the `block_roots` window of the finalized state
+            let contract = init_with_finalized_state(
+                init_input,
+                network_config,
+                slot + SLOTS_PER_HISTORICAL_ROOT + 1,
+                state_root,
+            );
+            assert_eq!(contract.recent_block_hash_safe(proof), None);
+        }
+
+        #[test]
+        pub fn test_historical_block_hash_safe() {
+            let (_headers, updates, init_input) = get_test_data(None);
+            let network_config = get_network_config_since(Fork::Capella);
+            let header_update = updates[0].finality_update.header_update.clone();
+            let slot = header_update.beacon_header.slot;
+            // The block roots of the period are summarized into the state when the period is over
+            let state_slot =
+                (slot / SLOTS_PER_HISTORICAL_ROOT + 1) * SLOTS_PER_HISTORICAL_ROOT + 100;
+
+            let (block_summary_root, block_roots_branch) = build_merkle_proof(
+                H256(header_update.beacon_header.tree_hash_root()),
+                (1 << BLOCK_ROOTS_TREE_DEPTH) | (slot % SLOTS_PER_HISTORICAL_ROOT),
+            );
+            // `historical_summaries[i].block_summary_root`, where `i` is the period of the block
+            // since Capella
+            let historical_summaries_gindex =
+                network_config.historical_summaries_gindex_by_slot(state_slot) as u64;
+            let (state_root, historical_summary_branch) = build_merkle_proof(
+                block_summary_root,
+                (((historical_summaries_gindex * 2) << HISTORICAL_SUMMARIES_TREE_DEPTH)
+                    | (slot / SLOTS_PER_HISTORICAL_ROOT))
+                    * 2,
+            );
+            let contract = init_with_finalized_state(
+                init_input.clone(),
+                network_config.clone(),
+                state_slot,
+                state_root,
+            );
+
+            let proof = HistoricalBlockProof {
+                header_update: header_update.clone(),
+                block_roots_branch,
+                historical_summary_branch,
+            };
+            assert_eq!(
+                contract.historical_block_hash_safe(proof.clone()),
+                Some(header_update.execution_block_hash)
+            );
+
+            let mut invalid_proof = proof.clone();
+            invalid_proof.header_update.execution_block_hash = H256::from([0x01; 32]);
+            assert_eq!(contract.historical_block_hash_safe(invalid_proof), None);
+
+            let mut invalid_proof = proof.clone();
+            invalid_proof.block_roots_branch[0] = H256::from([0x01; 32]);
+            assert_eq!(contract.historical_block_hash_safe(invalid_proof), None);
+
+            // The block roots of the current period are not summarized yet
+            let contract = init_with_finalized_state(init_input, network_config, slot, state_root);
+            assert_eq!(contract.historical_block_hash_safe(proof), None);
+        }
+
+        #[test]
+        pub fn test_blob_versioned_hash_safe_of_finalized_block() {
+            let (_headers, updates, mut init_input) = get_test_data(None);
+            let network_config = get_network_config_since(Fork::Deneb);
+            let commitment = KzgCommitment([0xab; KZG_COMMITMENT_BYTES_LEN]);
+            let (header_update, commitment_branch) = get_blob_header_update(
+                &updates[0].finality_update.header_update,
+                &network_config,
+                &commitment,
+                3,
+            );
+            let beacon_header = header_update.beacon_header.clone();
+            init_input.finalized_beacon_header.header = beacon_header.clone();
+            let contract = init_with_finalized_state(
+                init_input,
+                network_config,
+                beacon_header.slot,
+                beacon_header.state_root,
+            );
+
+            let proof = BlobCommitmentProof {
+                header_update,
+                block_roots_branch: vec![],
+                blob_kzg_commitment: commitment.clone(),
+                blob_index: 3,
+                commitment_branch,
+            };
+            assert_eq!(
+                contract.blob_versioned_hash_safe(proof.clone()),
+                Some(kzg_commitment_to_versioned_hash(&commitment))
+            );
+
+            let mut invalid_proof = proof.clone();
+            invalid_proof.blob_index = 4;
+            assert_eq!(contract.blob_versioned_hash_safe(invalid_proof), None);
+
+            let mut invalid_proof = proof.clone();
+            invalid_proof.blob_kzg_commitment = KzgCommitment([0xcd; KZG_COMMITMENT_BYTES_LEN]);
+            assert_eq!(contract.blob_versioned_hash_safe(invalid_proof), None);
+
+            let mut invalid_proof = proof;
+            invalid_proof.header_update.execution_block_hash = H256::from([0x01; 32]);
+            assert_eq!(contract.blob_versioned_hash_safe(invalid_proof), None);
+        }
+
+        #[test]
+        pub fn test_blob_versioned_hash_safe_with_block_roots_proof() {
+            let (_headers, updates, init_input) = get_test_data(None);
+            let network_config = get_network_config_since(Fork::Deneb);
+            let commitment = KzgCommitment([0xab; KZG_COMMITMENT_BYTES_LEN]);
+            let (header_update, commitment_branch) = get_blob_header_update(
+                &updates[0].finality_update.header_update,
+                &network_config,
+                &commitment,
+                0,
+            );
+            let slot = header_update.beacon_header.slot;
+            let state_slot = slot + 100;
+
+            let block_roots_gindex = network_config.block_roots_gindex_by_slot(state_slot) as u64;
+            let (state_root, block_roots_branch) = build_merkle_proof(
+                H256(header_update.beacon_header.tree_hash_root()),
+                (block_roots_gindex << BLOCK_ROOTS_TREE_DEPTH) | (slot % SLOTS_PER_HISTORICAL_ROOT),
+            );
+            let contract =
+                init_with_finalized_state(init_input, network_config, state_slot, state_root);
+
+            let proof = BlobCommitmentProof {
+                header_update,
+                block_roots_branch,
+                blob_kzg_commitment: commitment.clone(),
+                blob_index: 0,
+                commitment_branch,
+            };
+            assert_eq!(
+                contract.blob_versioned_hash_safe(proof.clone()),
+                Some(kzg_commitment_to_versioned_hash(&commitment))
+            );
+
+            let mut invalid_proof = proof.clone();
+            invalid_proof.block_roots_branch[0] = H256::from([0x01; 32]);
+            assert_eq!(contract.blob_versioned_hash_safe(invalid_proof), None);
+
+            // The block isn't the finalized one, so the block roots proof is required
+            let mut invalid_proof = proof;
+            invalid_proof.block_roots_branch = vec![];
+            assert_eq!(contract.blob_versioned_hash_safe(invalid_proof), None);
+        }
+
+        fn get_validator() -> Validator {
+            Validator {
+                pubkey: PublicKeyBytes([0xab; PUBLIC_KEY_BYTES_LEN]),
+                withdrawal_credentials: H256::from([0x01; 32]),
+                effective_balance: 32_000_000_000,
+                slashed: false,
+                activation_eligibility_epoch: 10,
+                activation_epoch: 20,
+                exit_epoch: u64::MAX,
+                withdrawable_epoch: u64::MAX,
+            }
+        }
+
+        #[test]
+        pub fn test_verify_validator() {
+            let (_headers, updates, init_input) = get_test_data(None);
+            let network_config = get_network_config_since(Fork::Capella);
+            let state_slot = updates[0].finality_update.header_update.beacon_header.slot;
+
+            let validator = get_validator();
+            let gindex = network_config.validator_gindex(1234, state_slot).unwrap();
+            let (state_root, validator_branch) =
+                build_merkle_proof(H256(validator.tree_hash_root()), gindex);
+            let contract =
+                init_with_finalized_state(init_input, network_config, state_slot, state_root);
+
+            assert!(contract.verify_validator(1234, validator.clone(), validator_branch.clone()));
+            assert!(!contract.verify_validator(1235, validator.clone(), validator_branch.clone()));
+            assert!(!contract.verify_validator(
+                1234,
+                Validator {
+                    slashed: true,
+                    ..validator.clone()
+                },
+                validator_branch.clone()
+            ));
+            // The index exceeds the validator registry limit
+            assert!(!contract.verify_validator(
+                1 << VALIDATOR_REGISTRY_TREE_DEPTH,
+                validator,
+                validator_branch
+            ));
+        }
+
+        #[test]
+        pub fn test_validator_balance_safe() {
+            let (_headers, updates, init_input) = get_test_data(None);
+            let network_config = get_network_config_since(Fork::Capella);
+            let state_slot = updates[0].finality_update.header_update.beacon_header.slot;
+
+            // The balances of the validators 1232..1236
+            let balances: [u64; 4] = [1, 32_000_000_000, 31_999_999_999, 4];
+            let mut balances_chunk = [0u8; 32];
+            for (i, balance) in balances.iter().enumerate() {
+                balances_chunk[i * 8..(i + 1) * 8].copy_from_slice(&balance.to_le_bytes());
+            }
+            let balances_chunk = H256::from(balances_chunk);
+
+            let gindex = network_config
+                .balances_chunk_gindex(1234, state_slot)
+                .unwrap();
+            let (state_root, balance_branch) = build_merkle_proof(balances_chunk, gindex);
+            let contract =
+                init_with_finalized_state(init_input, network_config, state_slot, state_root);
+
+            assert_eq!(
+                contract.validator_balance_safe(1234, balances_chunk, balance_branch.clone()),
+                Some(31_999_999_999)
+            );
+            assert_eq!(
+                contract.validator_balance_safe(1235, balances_chunk, balance_branch.clone()),
+                Some(4)
+            );
+            // The validator from another chunk
+            assert_eq!(
+                contract.validator_balance_safe(1236, balances_chunk, balance_branch.clone()),
+                None
+            );
+
+            let mut invalid_branch = balance_branch;
+            invalid_branch[0] = H256::from([0xff; 32]);
+            assert_eq!(
+                contract.validator_balance_safe(1234, balances_chunk, invalid_branch),
+                None
+            );
+        }
     }
 
     #[cfg(feature = "mainnet")]
@@ -479,7 +1693,7 @@ mod tests {
         }
 
         #[test]
-        #[cfg_attr(feature = "bls", ignore)]
+        #[cfg_attr(any(feature = "bls", feature = "bls_host_functions"), ignore)]
         #[should_panic(
             expected = "The client can't be executed in the trustless mode without BLS sigs verification on Mainnet"
         )]
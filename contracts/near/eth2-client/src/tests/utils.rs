@@ -33,6 +33,10 @@ pub fn read_client_updates(
     updates
 }
 
+/// The BLS12-381 host functions are not available in the mocked blockchain, so the signatures
+/// are verified only by the integration tests when the `bls_host_functions` feature is enabled
+pub const VERIFY_BLS_SIGNATURES: bool = !cfg!(feature = "bls_host_functions");
+
 pub struct InitOptions {
     pub validate_updates: bool,
     pub verify_bls_signatures: bool,
@@ -67,13 +71,14 @@ pub fn get_goerli_test_data(
 
     let init_options = init_options.unwrap_or(InitOptions {
         validate_updates: true,
-        verify_bls_signatures: true,
+        verify_bls_signatures: VERIFY_BLS_SIGNATURES,
         hashes_gc_threshold: 51000,
         trusted_signer: None,
     });
 
     let init_input = InitInput {
         network: NETWORK.to_string(),
+        network_config: None,
         finalized_execution_header: HEADERS[0][0].clone(),
         finalized_beacon_header: UPDATES[0].clone().finality_update.header_update.into(),
         current_sync_committee: INIT_UPDATE
@@ -93,6 +98,9 @@ pub fn get_goerli_test_data(
         verify_bls_signatures: init_options.verify_bls_signatures,
         hashes_gc_threshold: init_options.hashes_gc_threshold,
         trusted_signer: init_options.trusted_signer,
+        sparse_mode: false,
+        store_header_roots: false,
+        parameter_change_delay: Some(0),
     };
 
     (&HEADERS, &UPDATES, init_input)
@@ -1,6 +1,10 @@
 #!/usr/bin/env bash
 
 rustup target add wasm32-unknown-unknown
+cargo build --target wasm32-unknown-unknown --release --no-default-features --features logs,bls
+cp ../target/wasm32-unknown-unknown/release/eth2_client.wasm ../target/wasm32-unknown-unknown/release/eth2_client_bls.wasm
+cargo build --target wasm32-unknown-unknown --release --no-default-features --features logs,bls_host_functions
+cp ../target/wasm32-unknown-unknown/release/eth2_client.wasm ../target/wasm32-unknown-unknown/release/eth2_client_bls_host_functions.wasm
 cargo build --target wasm32-unknown-unknown --release --no-default-features --features logs
 
 RUST_BACKTRACE=1 cargo test --jobs 8 --package eth2-client -- --nocapture
@@ -8,3 +12,6 @@ RUST_BACKTRACE=1 cargo test --jobs 8 --package eth2-client -- --nocapture
 RUST_BACKTRACE=1 cargo test --no-default-features --jobs 8 --package eth2-client -- --nocapture
 
 RUST_BACKTRACE=1 cargo test --no-default-features --features bls --jobs 8 --package eth2-client -- --nocapture
+
+# The host functions are verified by the integration tests, the unit tests skip the BLS signatures verification
+RUST_BACKTRACE=1 cargo test --no-default-features --features bls_host_functions --jobs 8 --package eth2-client -- --nocapture
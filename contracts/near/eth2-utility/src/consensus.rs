@@ -7,9 +7,14 @@ use eth_types::eth2::*;
 use eth_types::H256;
 use tree_hash::TreeHash;
 
+#[cfg(not(target_arch = "wasm32"))]
+use serde::{Deserialize, Serialize};
+
 pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
 pub const MIN_SYNC_COMMITTEE_PARTICIPANTS: u64 = 1;
 pub const SLOTS_PER_EPOCH: u64 = 32;
+/// The number of slots after the finalized header when the best valid update can be forced
+pub const UPDATE_TIMEOUT: u64 = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
 pub const DOMAIN_SYNC_COMMITTEE: DomainType = [0x07, 0x00, 0x00, 0x00];
 
 pub const FINALIZED_ROOT_INDEX: u32 = 105;
@@ -19,6 +24,32 @@ pub const FINALITY_TREE_INDEX: u32 = get_subtree_index(FINALIZED_ROOT_INDEX);
 pub const SYNC_COMMITTEE_TREE_DEPTH: u32 = floorlog2(NEXT_SYNC_COMMITTEE_INDEX);
 pub const SYNC_COMMITTEE_TREE_INDEX: u32 = get_subtree_index(NEXT_SYNC_COMMITTEE_INDEX);
 
+// Since Electra the BeaconState has more than 32 fields, so its tree is one level deeper
+// and the generalized indices of the light client fields change.
+pub const FINALIZED_ROOT_INDEX_ELECTRA: u32 = 169;
+pub const NEXT_SYNC_COMMITTEE_INDEX_ELECTRA: u32 = 87;
+
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
+pub const BLOCK_ROOTS_TREE_DEPTH: u32 = 13;
+pub const BLOCK_ROOTS_INDEX: u32 = 37;
+pub const BLOCK_ROOTS_INDEX_ELECTRA: u32 = 69;
+// `historical_summaries` is a list limited by HISTORICAL_ROOTS_LIMIT = 2**24
+pub const HISTORICAL_SUMMARIES_TREE_DEPTH: u32 = 24;
+pub const HISTORICAL_SUMMARIES_INDEX: u32 = 59;
+pub const HISTORICAL_SUMMARIES_INDEX_ELECTRA: u32 = 91;
+
+pub const VALIDATOR_REGISTRY_TREE_DEPTH: u32 = 40;
+/// Four balances are packed into each chunk of the `balances` list
+pub const BALANCES_TREE_DEPTH: u32 = VALIDATOR_REGISTRY_TREE_DEPTH - 2;
+pub const VALIDATORS_INDEX: u32 = 43;
+pub const VALIDATORS_INDEX_ELECTRA: u32 = 75;
+pub const BALANCES_INDEX: u32 = 44;
+pub const BALANCES_INDEX_ELECTRA: u32 = 76;
+
+pub const MAX_BLOB_COMMITMENTS_PER_BLOCK: u64 = 4096;
+pub const BLOB_KZG_COMMITMENTS_INDEX: u32 = 27;
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
 pub struct ProofSize {
     pub beacon_block_body_tree_depth: usize,
     pub l1_beacon_block_body_tree_execution_payload_index: usize,
@@ -28,11 +59,13 @@ pub struct ProofSize {
     pub execution_proof_size: usize,
 }
 
-#[derive(PartialEq, BorshSerialize, BorshDeserialize)]
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
 pub enum Network {
     Mainnet,
     Goerli,
     Sepolia,
+    Holesky,
+    Hoodi,
 }
 
 impl FromStr for Network {
@@ -42,19 +75,54 @@ impl FromStr for Network {
             "mainnet" => Ok(Network::Mainnet),
             "goerli" => Ok(Network::Goerli),
             "sepolia" => Ok(Network::Sepolia),
+            "holesky" => Ok(Network::Holesky),
+            "hoodi" => Ok(Network::Hoodi),
             _ => Err(format!("Unknown network {}", input)),
         }
     }
 }
 
+/// Consensus forks supported by the light client, in activation order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(
+    not(target_arch = "wasm32"),
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Fork {
+    Bellatrix,
+    Capella,
+    Deneb,
+    Electra,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
+pub struct ForkConfig {
+    pub fork: Fork,
+    pub version: ForkVersion,
+    pub epoch: Epoch,
+}
+
+impl ForkConfig {
+    pub fn new(fork: Fork, version: ForkVersion, epoch: Epoch) -> Self {
+        Self {
+            fork,
+            version,
+            epoch,
+        }
+    }
+}
+
+/// Network parameters the light client depends on. The presets for the public networks are
+/// available through `NetworkConfig::new`, any other network (e.g. a private devnet) can be
+/// described explicitly.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize, Deserialize))]
 pub struct NetworkConfig {
-    pub genesis_validators_root: [u8; 32],
-    pub bellatrix_fork_version: ForkVersion,
-    pub bellatrix_fork_epoch: u64,
-    pub capella_fork_version: ForkVersion,
-    pub capella_fork_epoch: u64,
-    pub deneb_fork_version: ForkVersion,
-    pub deneb_fork_epoch: u64,
+    pub genesis_validators_root: H256,
+    /// Fork schedule ordered by activation epoch
+    pub forks: Vec<ForkConfig>,
 }
 
 impl NetworkConfig {
@@ -65,57 +133,118 @@ impl NetworkConfig {
                     0x4b, 0x36, 0x3d, 0xb9, 0x4e, 0x28, 0x61, 0x20, 0xd7, 0x6e, 0xb9, 0x05, 0x34,
                     0x0f, 0xdd, 0x4e, 0x54, 0xbf, 0xe9, 0xf0, 0x6b, 0xf3, 0x3f, 0xf6, 0xcf, 0x5a,
                     0xd2, 0x7f, 0x51, 0x1b, 0xfe, 0x95,
+                ]
+                .into(),
+                forks: vec![
+                    ForkConfig::new(Fork::Bellatrix, [0x02, 0x00, 0x00, 0x00], 144896),
+                    ForkConfig::new(Fork::Capella, [0x03, 0x00, 0x00, 0x00], 194048),
+                    ForkConfig::new(Fork::Deneb, [0x04, 0x00, 0x00, 0x00], 269568),
+                    ForkConfig::new(Fork::Electra, [0x05, 0x00, 0x00, 0x00], 364032),
                 ],
-                bellatrix_fork_version: [0x02, 0x00, 0x00, 0x00],
-                bellatrix_fork_epoch: 144896,
-                capella_fork_version: [0x03, 0x00, 0x00, 0x00],
-                capella_fork_epoch: 194048,
-                deneb_fork_version: [0x04, 0x00, 0x00, 0x00],
-                deneb_fork_epoch: 269568,
             },
+            // Goerli is deprecated and will never reach Electra
             Network::Goerli => Self {
                 genesis_validators_root: [
                     0x04, 0x3d, 0xb0, 0xd9, 0xa8, 0x38, 0x13, 0x55, 0x1e, 0xe2, 0xf3, 0x34, 0x50,
                     0xd2, 0x37, 0x97, 0x75, 0x7d, 0x43, 0x09, 0x11, 0xa9, 0x32, 0x05, 0x30, 0xad,
                     0x8a, 0x0e, 0xab, 0xc4, 0x3e, 0xfb,
+                ]
+                .into(),
+                forks: vec![
+                    ForkConfig::new(Fork::Bellatrix, [0x02, 0x00, 0x10, 0x20], 112260),
+                    ForkConfig::new(Fork::Capella, [0x03, 0x00, 0x10, 0x20], 162304),
+                    ForkConfig::new(Fork::Deneb, [0x04, 0x00, 0x10, 0x20], 231680),
                 ],
-                bellatrix_fork_version: [0x02, 0x00, 0x10, 0x20],
-                bellatrix_fork_epoch: 112260,
-                capella_fork_version: [0x03, 0x00, 0x10, 0x20],
-                capella_fork_epoch: 162304,
-                deneb_fork_version: [0x04, 0x00, 0x10, 0x20],
-                deneb_fork_epoch: 231680,
             },
             Network::Sepolia => Self {
                 genesis_validators_root: [
                     0xd8, 0xea, 0x17, 0x1f, 0x3c, 0x94, 0xae, 0xa2, 0x1e, 0xbc, 0x42, 0xa1, 0xed,
                     0x61, 0x05, 0x2a, 0xcf, 0x3f, 0x92, 0x09, 0xc0, 0x0e, 0x4e, 0xfb, 0xaa, 0xdd,
                     0xac, 0x09, 0xed, 0x9b, 0x80, 0x78,
+                ]
+                .into(),
+                forks: vec![
+                    ForkConfig::new(Fork::Bellatrix, [0x90, 0x00, 0x00, 0x71], 100),
+                    ForkConfig::new(Fork::Capella, [0x90, 0x00, 0x00, 0x72], 56832),
+                    ForkConfig::new(Fork::Deneb, [0x90, 0x00, 0x00, 0x73], 132608),
+                    ForkConfig::new(Fork::Electra, [0x90, 0x00, 0x00, 0x74], 222464),
+                ],
+            },
+            Network::Holesky => Self {
+                genesis_validators_root: [
+                    0x91, 0x43, 0xaa, 0x7c, 0x61, 0x5a, 0x7f, 0x71, 0x15, 0xe2, 0xb6, 0xaa, 0xc3,
+                    0x19, 0xc0, 0x35, 0x29, 0xdf, 0x82, 0x42, 0xae, 0x70, 0x5f, 0xba, 0x9d, 0xf3,
+                    0x9b, 0x79, 0xc5, 0x9f, 0xa8, 0xb1,
+                ]
+                .into(),
+                forks: vec![
+                    ForkConfig::new(Fork::Bellatrix, [0x03, 0x01, 0x70, 0x00], 0),
+                    ForkConfig::new(Fork::Capella, [0x04, 0x01, 0x70, 0x00], 256),
+                    ForkConfig::new(Fork::Deneb, [0x05, 0x01, 0x70, 0x00], 29696),
+                    ForkConfig::new(Fork::Electra, [0x06, 0x01, 0x70, 0x00], 115968),
+                ],
+            },
+            Network::Hoodi => Self {
+                genesis_validators_root: [
+                    0x21, 0x2f, 0x13, 0xfc, 0x4d, 0xf0, 0x78, 0xb6, 0xcb, 0x7d, 0xb2, 0x28, 0xf1,
+                    0xc8, 0x30, 0x75, 0x66, 0xdc, 0xec, 0xf9, 0x00, 0x86, 0x74, 0x01, 0xa9, 0x20,
+                    0x23, 0xd7, 0xba, 0x99, 0xcb, 0x5f,
+                ]
+                .into(),
+                forks: vec![
+                    ForkConfig::new(Fork::Bellatrix, [0x30, 0x00, 0x09, 0x10], 0),
+                    ForkConfig::new(Fork::Capella, [0x40, 0x00, 0x09, 0x10], 0),
+                    ForkConfig::new(Fork::Deneb, [0x50, 0x00, 0x09, 0x10], 0),
+                    ForkConfig::new(Fork::Electra, [0x60, 0x00, 0x09, 0x10], 2048),
                 ],
-                bellatrix_fork_version: [0x90, 0x00, 0x00, 0x71],
-                bellatrix_fork_epoch: 100,
-                capella_fork_version: [0x90, 0x00, 0x00, 0x72],
-                capella_fork_epoch: 56832,
-                deneb_fork_version: [0x90, 0x00, 0x00, 0x73],
-                deneb_fork_epoch: 132608,
             },
         }
     }
 
-    pub fn compute_fork_version(&self, epoch: Epoch) -> Option<ForkVersion> {
-        if epoch >= self.deneb_fork_epoch {
-            return Some(self.deneb_fork_version);
+    /// Checks that the fork schedule is not empty and that both forks and their activation
+    /// epochs are in ascending order.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.forks.is_empty() {
+            return Err("The fork schedule is empty".to_string());
         }
 
-        if epoch >= self.capella_fork_epoch {
-            return Some(self.capella_fork_version);
-        }
+        for pair in self.forks.windows(2) {
+            if pair[0].fork >= pair[1].fork {
+                return Err(format!(
+                    "The fork {:?} is scheduled after {:?}",
+                    pair[0].fork, pair[1].fork
+                ));
+            }
 
-        if epoch >= self.bellatrix_fork_epoch {
-            return Some(self.bellatrix_fork_version);
+            if pair[0].epoch > pair[1].epoch {
+                return Err(format!(
+                    "The fork {:?} activates before {:?}",
+                    pair[1].fork, pair[0].fork
+                ));
+            }
         }
 
-        None
+        Ok(())
+    }
+
+    /// Returns the latest fork activated at the given epoch
+    pub fn fork_at_epoch(&self, epoch: Epoch) -> Option<&ForkConfig> {
+        self.forks.iter().rev().find(|fork| epoch >= fork.epoch)
+    }
+
+    pub fn fork_epoch(&self, fork: Fork) -> Option<Epoch> {
+        self.forks
+            .iter()
+            .find(|fork_config| fork_config.fork == fork)
+            .map(|fork_config| fork_config.epoch)
+    }
+
+    pub fn is_fork_active(&self, fork: Fork, epoch: Epoch) -> bool {
+        matches!(self.fork_at_epoch(epoch), Some(fork_config) if fork_config.fork >= fork)
+    }
+
+    pub fn compute_fork_version(&self, epoch: Epoch) -> Option<ForkVersion> {
+        self.fork_at_epoch(epoch).map(|fork| fork.version)
     }
 
     pub fn compute_fork_version_by_slot(&self, slot: Slot) -> Option<ForkVersion> {
@@ -123,7 +252,9 @@ impl NetworkConfig {
     }
 
     pub fn compute_proof_size(&self, epoch: Epoch) -> ProofSize {
-        if epoch >= self.deneb_fork_epoch {
+        // Electra adds a field to the beacon block body, but it still fits into a tree of depth 4,
+        // and the execution payload layout is the same as in Deneb
+        if self.is_fork_active(Fork::Deneb, epoch) {
             return ProofSize {
                 beacon_block_body_tree_depth: 4,
                 l1_beacon_block_body_tree_execution_payload_index: 9,
@@ -148,6 +279,235 @@ impl NetworkConfig {
         self.compute_proof_size(compute_epoch_at_slot(slot))
     }
 
+    /// Returns the generalized index of `finalized_checkpoint.root` in the BeaconState at the given epoch
+    pub fn finalized_root_gindex(&self, epoch: Epoch) -> u32 {
+        if self.is_fork_active(Fork::Electra, epoch) {
+            FINALIZED_ROOT_INDEX_ELECTRA
+        } else {
+            FINALIZED_ROOT_INDEX
+        }
+    }
+
+    pub fn finalized_root_gindex_by_slot(&self, slot: Slot) -> u32 {
+        self.finalized_root_gindex(compute_epoch_at_slot(slot))
+    }
+
+    /// Returns the generalized index of `next_sync_committee` in the BeaconState at the given epoch
+    pub fn next_sync_committee_gindex(&self, epoch: Epoch) -> u32 {
+        if self.is_fork_active(Fork::Electra, epoch) {
+            NEXT_SYNC_COMMITTEE_INDEX_ELECTRA
+        } else {
+            NEXT_SYNC_COMMITTEE_INDEX
+        }
+    }
+
+    pub fn next_sync_committee_gindex_by_slot(&self, slot: Slot) -> u32 {
+        self.next_sync_committee_gindex(compute_epoch_at_slot(slot))
+    }
+
+    /// Returns the generalized index of `block_roots` in the BeaconState at the given epoch
+    pub fn block_roots_gindex(&self, epoch: Epoch) -> u32 {
+        if self.is_fork_active(Fork::Electra, epoch) {
+            BLOCK_ROOTS_INDEX_ELECTRA
+        } else {
+            BLOCK_ROOTS_INDEX
+        }
+    }
+
+    pub fn block_roots_gindex_by_slot(&self, slot: Slot) -> u32 {
+        self.block_roots_gindex(compute_epoch_at_slot(slot))
+    }
+
+    /// Verifies that `block_root` is the root of the beacon block at `slot` recorded in the
+    /// `block_roots` of the beacon state with `state_root` at `state_slot`.
+    ///
+    /// The state keeps the roots of the last `SLOTS_PER_HISTORICAL_ROOT` blocks only, so `slot`
+    /// should be in `[state_slot - SLOTS_PER_HISTORICAL_ROOT, state_slot)`.
+    pub fn verify_recent_block_root(
+        &self,
+        block_root: H256,
+        slot: Slot,
+        block_roots_branch: &[H256],
+        state_slot: Slot,
+        state_root: H256,
+    ) -> bool {
+        if slot >= state_slot || slot + SLOTS_PER_HISTORICAL_ROOT < state_slot {
+            return false;
+        }
+
+        let block_roots_gindex = self.block_roots_gindex_by_slot(state_slot);
+        let depth = BLOCK_ROOTS_TREE_DEPTH + floorlog2(block_roots_gindex);
+        let index = (get_subtree_index(block_roots_gindex) << BLOCK_ROOTS_TREE_DEPTH)
+            | (slot % SLOTS_PER_HISTORICAL_ROOT) as u32;
+
+        verify_merkle_proof(
+            block_root,
+            block_roots_branch,
+            depth as usize,
+            index as usize,
+            state_root,
+        )
+    }
+
+    /// Returns the generalized index of `historical_summaries` in the BeaconState at the given epoch
+    pub fn historical_summaries_gindex(&self, epoch: Epoch) -> u32 {
+        if self.is_fork_active(Fork::Electra, epoch) {
+            HISTORICAL_SUMMARIES_INDEX_ELECTRA
+        } else {
+            HISTORICAL_SUMMARIES_INDEX
+        }
+    }
+
+    pub fn historical_summaries_gindex_by_slot(&self, slot: Slot) -> u32 {
+        self.historical_summaries_gindex(compute_epoch_at_slot(slot))
+    }
+
+    /// Verifies that `block_root` is the root of the beacon block at `slot` recorded in the
+    /// `historical_summaries` of the beacon state with `state_root` at `state_slot`.
+    ///
+    /// `block_roots_branch` proves `block_root` in `historical_summaries[i].block_summary_root`, and
+    /// `historical_summary_branch` proves `block_summary_root` in the beacon state. Only blocks
+    /// since Capella from the `SLOTS_PER_HISTORICAL_ROOT` periods which are over at `state_slot`
+    /// can be verified.
+    pub fn verify_historical_block_root(
+        &self,
+        block_root: H256,
+        slot: Slot,
+        block_roots_branch: &[H256],
+        historical_summary_branch: &[H256],
+        state_slot: Slot,
+        state_root: H256,
+    ) -> bool {
+        let capella_slot = match self.fork_epoch(Fork::Capella) {
+            Some(epoch) => epoch * SLOTS_PER_EPOCH,
+            None => return false,
+        };
+
+        // The summary of the block roots is appended to the state only when the period is over
+        if slot < capella_slot
+            || slot / SLOTS_PER_HISTORICAL_ROOT >= state_slot / SLOTS_PER_HISTORICAL_ROOT
+        {
+            return false;
+        }
+
+        if block_roots_branch.len() != BLOCK_ROOTS_TREE_DEPTH as usize {
+            return false;
+        }
+
+        let block_summary_root = merkle_root_from_branch(
+            block_root,
+            block_roots_branch,
+            BLOCK_ROOTS_TREE_DEPTH as usize,
+            (slot % SLOTS_PER_HISTORICAL_ROOT) as usize,
+        );
+
+        let summary_index =
+            slot / SLOTS_PER_HISTORICAL_ROOT - capella_slot / SLOTS_PER_HISTORICAL_ROOT;
+        if summary_index >= 1 << HISTORICAL_SUMMARIES_TREE_DEPTH {
+            return false;
+        }
+        let historical_summaries_gindex = self.historical_summaries_gindex_by_slot(state_slot);
+
+        // `block_summary_root` is the first field of `HistoricalSummary`, the list data root is
+        // mixed in with the list length, and the list itself is a field of the `BeaconState`
+        let depth =
+            1 + HISTORICAL_SUMMARIES_TREE_DEPTH + 1 + floorlog2(historical_summaries_gindex);
+        let index = ((get_subtree_index(historical_summaries_gindex) as u64)
+            << (HISTORICAL_SUMMARIES_TREE_DEPTH + 2))
+            | (summary_index << 1);
+
+        verify_merkle_proof(
+            block_summary_root,
+            historical_summary_branch,
+            depth as usize,
+            index as usize,
+            state_root,
+        )
+    }
+
+    /// Returns the generalized index of `validators` in the BeaconState at the given epoch
+    pub fn validators_gindex(&self, epoch: Epoch) -> u32 {
+        if self.is_fork_active(Fork::Electra, epoch) {
+            VALIDATORS_INDEX_ELECTRA
+        } else {
+            VALIDATORS_INDEX
+        }
+    }
+
+    pub fn validators_gindex_by_slot(&self, slot: Slot) -> u32 {
+        self.validators_gindex(compute_epoch_at_slot(slot))
+    }
+
+    /// Returns the generalized index of `balances` in the BeaconState at the given epoch
+    pub fn balances_gindex(&self, epoch: Epoch) -> u32 {
+        if self.is_fork_active(Fork::Electra, epoch) {
+            BALANCES_INDEX_ELECTRA
+        } else {
+            BALANCES_INDEX
+        }
+    }
+
+    pub fn balances_gindex_by_slot(&self, slot: Slot) -> u32 {
+        self.balances_gindex(compute_epoch_at_slot(slot))
+    }
+
+    /// Returns the generalized index of `validators[validator_index]` in the beacon state at
+    /// `state_slot`, or `None` if the index exceeds the validator registry limit.
+    pub fn validator_gindex(&self, validator_index: u64, state_slot: Slot) -> Option<u64> {
+        if validator_index >= 1 << VALIDATOR_REGISTRY_TREE_DEPTH {
+            return None;
+        }
+
+        // The list data root is mixed in with the list length
+        let list_gindex = self.validators_gindex_by_slot(state_slot) as u64 * 2;
+        Some((list_gindex << VALIDATOR_REGISTRY_TREE_DEPTH) | validator_index)
+    }
+
+    /// Returns the generalized index of the chunk of `balances` with the balance of
+    /// `validator_index` in the beacon state at `state_slot`, or `None` if the index exceeds
+    /// the validator registry limit.
+    pub fn balances_chunk_gindex(&self, validator_index: u64, state_slot: Slot) -> Option<u64> {
+        if validator_index >= 1 << VALIDATOR_REGISTRY_TREE_DEPTH {
+            return None;
+        }
+
+        let list_gindex = self.balances_gindex_by_slot(state_slot) as u64 * 2;
+        Some((list_gindex << BALANCES_TREE_DEPTH) | (validator_index / 4))
+    }
+
+    /// Verifies that `commitment` is `blob_kzg_commitments[blob_index]` of the beacon block body
+    /// with `body_root` of the block at `slot`. The blobs are available only since Deneb.
+    pub fn verify_blob_kzg_commitment(
+        &self,
+        commitment: &KzgCommitment,
+        blob_index: u64,
+        commitment_branch: &[H256],
+        slot: Slot,
+        body_root: H256,
+    ) -> bool {
+        if !self.is_fork_active(Fork::Deneb, compute_epoch_at_slot(slot))
+            || blob_index >= MAX_BLOB_COMMITMENTS_PER_BLOCK
+        {
+            return false;
+        }
+
+        // The list data root is mixed in with the list length, and the list itself is a field
+        // of the `BeaconBlockBody`
+        let commitments_tree_depth = floorlog2(MAX_BLOB_COMMITMENTS_PER_BLOCK as u32) + 1;
+        let depth = commitments_tree_depth + floorlog2(BLOB_KZG_COMMITMENTS_INDEX);
+        let index = ((get_subtree_index(BLOB_KZG_COMMITMENTS_INDEX) as u64)
+            << commitments_tree_depth)
+            | blob_index;
+
+        verify_merkle_proof(
+            H256(commitment.tree_hash_root()),
+            commitment_branch,
+            depth as usize,
+            index as usize,
+            body_root,
+        )
+    }
+
     pub fn validate_beacon_block_header_update(&self, header_update: &HeaderUpdate) -> bool {
         let branch = &header_update.execution_hash_branch;
         let proof_size = self.compute_proof_size_by_slot(header_update.beacon_header.slot);
@@ -217,6 +577,22 @@ pub fn compute_domain(
     H256::from(domain)
 }
 
+/// Returns the balance of `validator_index` from the chunk of the `balances` list
+pub fn get_balance_from_chunk(balances_chunk: &H256, validator_index: u64) -> u64 {
+    let offset = (validator_index % 4) as usize * 8;
+    let balance: [u8; 8] = balances_chunk.0.as_bytes()[offset..offset + 8]
+        .try_into()
+        .unwrap();
+    u64::from_le_bytes(balance)
+}
+
+/// https://eips.ethereum.org/EIPS/eip-4844#helpers
+pub fn kzg_commitment_to_versioned_hash(commitment: &KzgCommitment) -> H256 {
+    let mut versioned_hash = ethereum_hashing::hash(&commitment.0);
+    versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+    H256(ethereum_types::H256::from_slice(&versioned_hash))
+}
+
 pub fn compute_signing_root(object_root: H256, domain: H256) -> H256 {
     eth_types::H256(
         SigningData {
@@ -240,6 +616,40 @@ pub fn get_participant_pubkeys(
     result
 }
 
+/// Returns the pubkeys whose sum is the aggregate pubkey of the sync committee participants.
+/// If the absent members are the minority, the committee aggregate pubkey and the negated pubkeys
+/// of the absent members are returned instead, which is much cheaper to decompress and aggregate
+pub fn get_pubkeys_for_aggregation(
+    sync_committee: &SyncCommittee,
+    sync_committee_bits: &BitVec<u8, Lsb0>,
+) -> Vec<PublicKeyBytes> {
+    let participants_count = sync_committee_bits.count_ones();
+    let absent_count = sync_committee_bits.len() - participants_count;
+    if absent_count + 1 >= participants_count {
+        return get_participant_pubkeys(&sync_committee.pubkeys.0, sync_committee_bits);
+    }
+
+    let mut result = vec![sync_committee.aggregate_pubkey.clone()];
+    for (idx, bit) in sync_committee_bits.iter().by_vals().enumerate() {
+        if !bit {
+            result.push(negate_pubkey(&sync_committee.pubkeys.0[idx]));
+        }
+    }
+    result
+}
+
+/// Negates the compressed G1 point by flipping the sign of its `y` coordinate
+pub fn negate_pubkey(pubkey: &PublicKeyBytes) -> PublicKeyBytes {
+    const INFINITY_FLAG: u8 = 0x40;
+    const SIGN_FLAG: u8 = 0x20;
+
+    let mut result = pubkey.clone();
+    if result.0[0] & INFINITY_FLAG == 0 {
+        result.0[0] ^= SIGN_FLAG;
+    }
+    result
+}
+
 /// Verify a proof that `leaf` exists at `index` in a Merkle tree rooted at `root`.
 ///
 /// The `branch` argument is the main component of the proof: it should be a list of internal
@@ -258,6 +668,30 @@ pub fn verify_merkle_proof(
     }
 }
 
+/// Verify a proof that `leaf` is the node with the generalized index `gindex` in a Merkle tree
+/// rooted at `root`.
+///
+/// Unlike `verify_merkle_proof`, the index is `u64`, so the proofs deeper than 32 levels, e.g.
+/// of the validator records, are verified correctly on wasm32.
+pub fn verify_merkle_proof_by_gindex(leaf: H256, branch: &[H256], gindex: u64, root: H256) -> bool {
+    if gindex == 0 || branch.len() != (63 - gindex.leading_zeros()) as usize {
+        return false;
+    }
+
+    let mut merkle_root = leaf.0.as_bytes().to_vec();
+    for (i, node) in branch.iter().enumerate() {
+        if (gindex >> i) & 0x01 == 1 {
+            merkle_root =
+                ethereum_hashing::hash32_concat(node.0.as_bytes(), &merkle_root)[..].to_vec();
+        } else {
+            merkle_root =
+                ethereum_hashing::hash32_concat(&merkle_root, node.0.as_bytes())[..].to_vec();
+        }
+    }
+
+    H256(ethereum_types::H256::from_slice(&merkle_root)) == root
+}
+
 /// Compute a root hash from a leaf and a Merkle proof.
 pub fn merkle_root_from_branch(leaf: H256, branch: &[H256], depth: usize, index: usize) -> H256 {
     assert_eq!(branch.len(), depth, "proof length should equal depth");
@@ -278,3 +712,396 @@ pub fn merkle_root_from_branch(leaf: H256, branch: &[H256], depth: usize, index:
 
     H256(ethereum_types::H256::from_slice(&merkle_root))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_light_client_gindices_switch_at_electra() {
+        let config = NetworkConfig::new(&Network::Mainnet);
+        let electra_fork_epoch = config.fork_epoch(Fork::Electra).unwrap();
+        let last_deneb_slot = electra_fork_epoch * SLOTS_PER_EPOCH - 1;
+        let first_electra_slot = electra_fork_epoch * SLOTS_PER_EPOCH;
+
+        assert_eq!(
+            config.finalized_root_gindex_by_slot(last_deneb_slot),
+            FINALIZED_ROOT_INDEX
+        );
+        assert_eq!(
+            config.next_sync_committee_gindex_by_slot(last_deneb_slot),
+            NEXT_SYNC_COMMITTEE_INDEX
+        );
+        assert_eq!(
+            config.finalized_root_gindex_by_slot(first_electra_slot),
+            FINALIZED_ROOT_INDEX_ELECTRA
+        );
+        assert_eq!(
+            config.next_sync_committee_gindex_by_slot(first_electra_slot),
+            NEXT_SYNC_COMMITTEE_INDEX_ELECTRA
+        );
+
+        assert_eq!(floorlog2(FINALIZED_ROOT_INDEX_ELECTRA), 7);
+        assert_eq!(get_subtree_index(FINALIZED_ROOT_INDEX_ELECTRA), 41);
+        assert_eq!(floorlog2(NEXT_SYNC_COMMITTEE_INDEX_ELECTRA), 6);
+        assert_eq!(get_subtree_index(NEXT_SYNC_COMMITTEE_INDEX_ELECTRA), 23);
+    }
+
+    #[test]
+    fn test_compute_fork_version_electra() {
+        let config = NetworkConfig::new(&Network::Sepolia);
+        let electra_fork_epoch = config.fork_epoch(Fork::Electra).unwrap();
+        assert_eq!(
+            config.compute_fork_version(electra_fork_epoch - 1),
+            Some([0x90, 0x00, 0x00, 0x73])
+        );
+        assert_eq!(
+            config.compute_fork_version(electra_fork_epoch),
+            Some([0x90, 0x00, 0x00, 0x74])
+        );
+
+        let goerli_config = NetworkConfig::new(&Network::Goerli);
+        assert_eq!(
+            goerli_config.compute_fork_version(u64::MAX - 1),
+            Some([0x04, 0x00, 0x10, 0x20])
+        );
+    }
+
+    #[test]
+    fn test_custom_network_config() {
+        let mut config = NetworkConfig {
+            genesis_validators_root: H256::from([0x01; 32]),
+            forks: vec![
+                ForkConfig::new(Fork::Capella, [0x40, 0x00, 0x00, 0x38], 0),
+                ForkConfig::new(Fork::Deneb, [0x50, 0x00, 0x00, 0x38], 10),
+            ],
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.compute_fork_version(0),
+            Some([0x40, 0x00, 0x00, 0x38])
+        );
+        assert_eq!(config.compute_proof_size(9).execution_proof_size, 8);
+        assert_eq!(config.compute_proof_size(10).execution_proof_size, 9);
+        assert_eq!(config.finalized_root_gindex(u64::MAX), FINALIZED_ROOT_INDEX);
+
+        config.forks[1].epoch = 0;
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.compute_fork_version(0),
+            Some([0x50, 0x00, 0x00, 0x38])
+        );
+
+        config.forks.swap(0, 1);
+        assert!(config.validate().is_err());
+
+        config.forks.clear();
+        assert!(config.validate().is_err());
+        assert_eq!(config.compute_fork_version(0), None);
+    }
+
+    #[test]
+    fn test_verify_historical_block_root() {
+        let config = NetworkConfig::new(&Network::Mainnet);
+        let capella_slot = config.fork_epoch(Fork::Capella).unwrap() * SLOTS_PER_EPOCH;
+        let slot = capella_slot + 5 * SLOTS_PER_HISTORICAL_ROOT + 1234;
+        let state_slot = config.fork_epoch(Fork::Electra).unwrap() * SLOTS_PER_EPOCH + 100;
+        let block_root = H256::from([0xaa; 32]);
+
+        let block_roots_branch: Vec<H256> = (0..BLOCK_ROOTS_TREE_DEPTH)
+            .map(|i| H256::from([i as u8; 32]))
+            .collect();
+        let block_summary_root = merkle_root_from_branch(
+            block_root,
+            &block_roots_branch,
+            BLOCK_ROOTS_TREE_DEPTH as usize,
+            1234,
+        );
+
+        // Build the generalized index of `historical_summaries[5].block_summary_root` step by step
+        let gindex: u64 = ((HISTORICAL_SUMMARIES_INDEX_ELECTRA as u64 * 2)
+            << HISTORICAL_SUMMARIES_TREE_DEPTH
+            | 5)
+            * 2;
+        let depth = 63 - gindex.leading_zeros() as usize;
+        let index = (gindex - (1 << depth)) as usize;
+        let historical_summary_branch: Vec<H256> = (0..depth)
+            .map(|i| H256::from([0x80 + i as u8; 32]))
+            .collect();
+        let state_root =
+            merkle_root_from_branch(block_summary_root, &historical_summary_branch, depth, index);
+
+        assert!(config.verify_historical_block_root(
+            block_root,
+            slot,
+            &block_roots_branch,
+            &historical_summary_branch,
+            state_slot,
+            state_root
+        ));
+        assert!(!config.verify_historical_block_root(
+            block_root,
+            slot + 1,
+            &block_roots_branch,
+            &historical_summary_branch,
+            state_slot,
+            state_root
+        ));
+        assert!(!config.verify_historical_block_root(
+            H256::from([0xbb; 32]),
+            slot,
+            &block_roots_branch,
+            &historical_summary_branch,
+            state_slot,
+            state_root
+        ));
+        // The state before Electra has a different layout
+        assert!(!config.verify_historical_block_root(
+            block_root,
+            slot,
+            &block_roots_branch,
+            &historical_summary_branch,
+            capella_slot + 6 * SLOTS_PER_HISTORICAL_ROOT,
+            state_root
+        ));
+        // The period of the block is not over yet
+        assert!(!config.verify_historical_block_root(
+            block_root,
+            slot,
+            &block_roots_branch,
+            &historical_summary_branch,
+            slot + 1,
+            state_root
+        ));
+    }
+
+    #[test]
+    fn test_verify_recent_block_root() {
+        let config = NetworkConfig::new(&Network::Sepolia);
+        let state_slot = config.fork_epoch(Fork::Deneb).unwrap() * SLOTS_PER_EPOCH + 20000;
+        let slot = state_slot - 100;
+        let block_root = H256::from([0xaa; 32]);
+
+        let gindex = ((BLOCK_ROOTS_INDEX as u64) << BLOCK_ROOTS_TREE_DEPTH)
+            + slot % SLOTS_PER_HISTORICAL_ROOT;
+        let depth = 63 - gindex.leading_zeros() as usize;
+        let index = (gindex - (1 << depth)) as usize;
+        let block_roots_branch: Vec<H256> = (0..depth).map(|i| H256::from([i as u8; 32])).collect();
+        let state_root = merkle_root_from_branch(block_root, &block_roots_branch, depth, index);
+
+        assert!(config.verify_recent_block_root(
+            block_root,
+            slot,
+            &block_roots_branch,
+            state_slot,
+            state_root
+        ));
+        assert!(!config.verify_recent_block_root(
+            block_root,
+            slot + 1,
+            &block_roots_branch,
+            state_slot,
+            state_root
+        ));
+        // The root of the block is out of the `block_roots` window of the state
+        assert!(!config.verify_recent_block_root(
+            block_root,
+            slot,
+            &block_roots_branch,
+            slot + SLOTS_PER_HISTORICAL_ROOT + 1,
+            state_root
+        ));
+        assert!(!config.verify_recent_block_root(
+            block_root,
+            slot,
+            &block_roots_branch,
+            slot,
+            state_root
+        ));
+    }
+
+    #[test]
+    fn test_verify_blob_kzg_commitment() {
+        let config = NetworkConfig::new(&Network::Mainnet);
+        let slot = config.fork_epoch(Fork::Deneb).unwrap() * SLOTS_PER_EPOCH + 100;
+        let commitment = KzgCommitment([0xab; KZG_COMMITMENT_BYTES_LEN]);
+
+        // `blob_kzg_commitments[3]`, the list length mixin and the field of the body
+        let gindex: u64 = ((BLOB_KZG_COMMITMENTS_INDEX as u64 * 2)
+            << floorlog2(MAX_BLOB_COMMITMENTS_PER_BLOCK as u32))
+            | 3;
+        let depth = 63 - gindex.leading_zeros() as usize;
+        let index = (gindex - (1 << depth)) as usize;
+        assert_eq!(depth, 17);
+        let commitment_branch: Vec<H256> = (0..depth).map(|i| H256::from([i as u8; 32])).collect();
+        let body_root = merkle_root_from_branch(
+            H256(commitment.tree_hash_root()),
+            &commitment_branch,
+            depth,
+            index,
+        );
+
+        assert!(config.verify_blob_kzg_commitment(
+            &commitment,
+            3,
+            &commitment_branch,
+            slot,
+            body_root
+        ));
+        assert!(!config.verify_blob_kzg_commitment(
+            &commitment,
+            4,
+            &commitment_branch,
+            slot,
+            body_root
+        ));
+        assert!(!config.verify_blob_kzg_commitment(
+            &KzgCommitment([0xcd; KZG_COMMITMENT_BYTES_LEN]),
+            3,
+            &commitment_branch,
+            slot,
+            body_root
+        ));
+        // There are no blobs before Deneb
+        assert!(!config.verify_blob_kzg_commitment(
+            &commitment,
+            3,
+            &commitment_branch,
+            slot - 200,
+            body_root
+        ));
+    }
+
+    #[test]
+    fn test_kzg_commitment_to_versioned_hash() {
+        // The commitment to the empty blob
+        let mut commitment = [0u8; KZG_COMMITMENT_BYTES_LEN];
+        commitment[0] = 0xc0;
+        assert_eq!(
+            kzg_commitment_to_versioned_hash(&KzgCommitment(commitment)),
+            H256::from(
+                hex::decode("010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014")
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_verify_validator_and_balance() {
+        let config = NetworkConfig::new(&Network::Mainnet);
+        let state_slot = config.fork_epoch(Fork::Electra).unwrap() * SLOTS_PER_EPOCH + 100;
+        let validator = Validator {
+            pubkey: PublicKeyBytes([0xab; PUBLIC_KEY_BYTES_LEN]),
+            withdrawal_credentials: H256::from([0x01; 32]),
+            effective_balance: 32_000_000_000,
+            slashed: false,
+            activation_eligibility_epoch: 10,
+            activation_epoch: 20,
+            exit_epoch: u64::MAX,
+            withdrawable_epoch: u64::MAX,
+        };
+        let validator_root = H256(validator.tree_hash_root());
+
+        let gindex = config.validator_gindex(1234, state_slot).unwrap();
+        assert_eq!(
+            gindex,
+            ((VALIDATORS_INDEX_ELECTRA as u64) << (VALIDATOR_REGISTRY_TREE_DEPTH + 1)) + 1234
+        );
+        let depth = 63 - gindex.leading_zeros() as usize;
+        let index = (gindex - (1 << depth)) as usize;
+        let validator_branch: Vec<H256> = (0..depth).map(|i| H256::from([i as u8; 32])).collect();
+        let state_root = merkle_root_from_branch(validator_root, &validator_branch, depth, index);
+
+        assert!(verify_merkle_proof_by_gindex(
+            validator_root,
+            &validator_branch,
+            gindex,
+            state_root
+        ));
+        assert!(!verify_merkle_proof_by_gindex(
+            validator_root,
+            &validator_branch,
+            config.validator_gindex(1235, state_slot).unwrap(),
+            state_root
+        ));
+        // The state before Electra has a different layout
+        assert!(!verify_merkle_proof_by_gindex(
+            validator_root,
+            &validator_branch,
+            config.validator_gindex(1234, state_slot - 200).unwrap(),
+            state_root
+        ));
+        assert_eq!(
+            config.validator_gindex(1 << VALIDATOR_REGISTRY_TREE_DEPTH, state_slot),
+            None
+        );
+
+        // The balances of the validators 1232..1236 are packed into one chunk
+        let mut balances_chunk = [0u8; 32];
+        balances_chunk[16..24].copy_from_slice(&31_500_000_000u64.to_le_bytes());
+        let balances_chunk = H256::from(balances_chunk);
+        assert_eq!(
+            get_balance_from_chunk(&balances_chunk, 1234),
+            31_500_000_000
+        );
+        assert_eq!(get_balance_from_chunk(&balances_chunk, 1235), 0);
+
+        let gindex = config.balances_chunk_gindex(1234, state_slot).unwrap();
+        assert_eq!(config.balances_chunk_gindex(1232, state_slot), Some(gindex));
+        let depth = 63 - gindex.leading_zeros() as usize;
+        let index = (gindex - (1 << depth)) as usize;
+        let balance_branch: Vec<H256> = (0..depth).map(|i| H256::from([i as u8; 32])).collect();
+        let state_root = merkle_root_from_branch(balances_chunk, &balance_branch, depth, index);
+        assert!(verify_merkle_proof_by_gindex(
+            balances_chunk,
+            &balance_branch,
+            gindex,
+            state_root
+        ));
+    }
+
+    #[test]
+    fn test_get_pubkeys_for_aggregation() {
+        let pubkeys: Vec<PublicKeyBytes> = (0..8u8)
+            .map(|i| {
+                let mut pubkey = [i; PUBLIC_KEY_BYTES_LEN];
+                pubkey[0] = 0x80;
+                PublicKeyBytes(pubkey)
+            })
+            .collect();
+        let sync_committee = SyncCommittee {
+            pubkeys: SyncCommitteePublicKeys(pubkeys.clone()),
+            aggregate_pubkey: PublicKeyBytes([0xab; PUBLIC_KEY_BYTES_LEN]),
+        };
+
+        let to_bytes = |pubkeys: Vec<PublicKeyBytes>| -> Vec<[u8; PUBLIC_KEY_BYTES_LEN]> {
+            pubkeys.into_iter().map(|pubkey| pubkey.0).collect()
+        };
+
+        // The most of the committee participates, so the absent pubkeys are subtracted
+        let bits = BitVec::<u8, Lsb0>::from_slice(&[0b1110_1111]);
+        assert_eq!(
+            to_bytes(get_pubkeys_for_aggregation(&sync_committee, &bits)),
+            to_bytes(vec![
+                sync_committee.aggregate_pubkey.clone(),
+                negate_pubkey(&pubkeys[4])
+            ])
+        );
+        assert_eq!(negate_pubkey(&pubkeys[4]).0[0], 0xa0);
+        assert_eq!(negate_pubkey(&negate_pubkey(&pubkeys[4])).0, pubkeys[4].0);
+
+        let bits = BitVec::<u8, Lsb0>::from_slice(&[0b0000_0110]);
+        assert_eq!(
+            to_bytes(get_pubkeys_for_aggregation(&sync_committee, &bits)),
+            to_bytes(vec![pubkeys[1].clone(), pubkeys[2].clone()])
+        );
+    }
+
+    #[test]
+    fn test_network_presets_are_valid() {
+        for network in ["mainnet", "goerli", "sepolia", "holesky", "hoodi"] {
+            let network = Network::from_str(network).unwrap();
+            assert!(NetworkConfig::new(&network).validate().is_ok());
+        }
+    }
+}
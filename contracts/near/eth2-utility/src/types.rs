@@ -1,3 +1,4 @@
+use crate::consensus::NetworkConfig;
 use borsh::{BorshDeserialize, BorshSerialize};
 use eth_types::eth2::*;
 use eth_types::H256;
@@ -13,7 +14,9 @@ pub struct ExecutionHeaderInfo {
 
 #[derive(Clone, BorshDeserialize, BorshSerialize)]
 pub struct InitInput {
+    /// Name of a network with a built-in config, used when `network_config` is not provided
     pub network: String,
+    pub network_config: Option<NetworkConfig>,
     pub finalized_execution_header: eth_types::BlockHeader,
     pub finalized_beacon_header: ExtendedBeaconBlockHeader,
     pub current_sync_committee: SyncCommittee,
@@ -22,6 +25,12 @@ pub struct InitInput {
     pub verify_bls_signatures: bool,
     pub hashes_gc_threshold: u64,
     pub trusted_signer: Option<AccountId>,
+    /// Store only the execution blocks of the finalized checkpoints
+    pub sparse_mode: bool,
+    /// Store receipts root, state root and timestamp of the finalized execution blocks
+    pub store_header_roots: bool,
+    /// The delay of the DAO parameter changes in nanoseconds, a day is used if not provided
+    pub parameter_change_delay: Option<u64>,
 }
 
 #[derive(Clone, BorshDeserialize, BorshSerialize, PartialEq)]
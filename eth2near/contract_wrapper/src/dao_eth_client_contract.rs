@@ -2,6 +2,7 @@ use crate::dao_contract::DAOContract;
 use crate::dao_types;
 use crate::eth_client_contract::EthClientContract;
 use crate::eth_client_contract_trait::EthClientContractTrait;
+use eth2_utility::consensus::NetworkConfig;
 use eth2_utility::types::ClientMode;
 use eth_types::eth2::{LightClientState, LightClientUpdate};
 use eth_types::{BlockHeader, H256};
@@ -77,6 +78,31 @@ impl EthClientContractTrait for DaoEthClientContract {
         Ok(execution_outcome)
     }
 
+    fn send_light_client_updates(
+        &mut self,
+        light_client_updates: Vec<LightClientUpdate>,
+    ) -> Result<FinalExecutionOutcomeView, Box<dyn Error>> {
+        // The DAO proposals contain a single update, so the updates are proposed one by one
+        let mut execution_outcome = None;
+        for light_client_update in light_client_updates {
+            execution_outcome = Some(self.send_light_client_update(light_client_update)?);
+        }
+
+        execution_outcome.ok_or_else(|| "The list of light client updates is empty".into())
+    }
+
+    fn send_best_light_client_update(
+        &mut self,
+        light_client_update: LightClientUpdate,
+    ) -> Result<FinalExecutionOutcomeView, Box<dyn Error>> {
+        self.eth_client_contract
+            .send_best_light_client_update(light_client_update)
+    }
+
+    fn force_light_client_update(&mut self) -> Result<FinalExecutionOutcomeView, Box<dyn Error>> {
+        self.eth_client_contract.force_light_client_update()
+    }
+
     fn get_finalized_beacon_block_hash(&self) -> Result<H256, Box<dyn Error>> {
         self.eth_client_contract.get_finalized_beacon_block_hash()
     }
@@ -107,6 +133,14 @@ impl EthClientContractTrait for DaoEthClientContract {
     fn get_unfinalized_tail_block_number(&self) -> Result<Option<u64>, Box<dyn Error>> {
         self.eth_client_contract.get_unfinalized_tail_block_number()
     }
+
+    fn get_network_config(&self) -> Result<NetworkConfig, Box<dyn Error>> {
+        self.eth_client_contract.get_network_config()
+    }
+
+    fn is_sparse_mode(&self) -> Result<bool, Box<dyn Error>> {
+        self.eth_client_contract.is_sparse_mode()
+    }
 }
 
 #[cfg(test)]
@@ -212,6 +246,7 @@ mod tests {
 
         eth_client.init_contract(
             eth_network::EthNetwork::Kiln,
+            None,
             finalized_execution_header.unwrap(),
             finalized_beacon_header,
             current_sync_committee,
@@ -220,6 +255,8 @@ mod tests {
             Some(false),
             None,
             Some(eth_client.contract_wrapper.get_signer_account_id()),
+            None,
+            None,
         );
 
         let dao_contract_wrapper =
@@ -2,6 +2,7 @@ use crate::contract_wrapper_trait::ContractWrapper;
 use crate::eth_client_contract_trait::EthClientContractTrait;
 use crate::eth_network::EthNetwork;
 use borsh::BorshDeserialize;
+use eth2_utility::consensus::NetworkConfig;
 use eth2_utility::types::ClientMode;
 use eth_types::eth2::{
     ExtendedBeaconBlockHeader, LightClientState, LightClientUpdate, SyncCommittee,
@@ -32,6 +33,7 @@ impl EthClientContract {
     ///
     /// # Arguments
     /// * `network` - the name of Ethereum network such as `mainnet`, `goerli`, `kiln`, etc.
+    /// * `network_config` - the network config to use instead of the built-in one for `network`.
     /// * `finalized_execution_header` - the finalized execution header to start initialization with.
     /// * `finalized_beacon_header` - correspondent finalized beacon header.
     /// * `current_sync_committee` - sync committee correspondent for finalized block.
@@ -39,9 +41,12 @@ impl EthClientContract {
     /// * `hashes_gs_threshold` - the maximum number of stored finalized blocks.
     /// * `max_submitted_block_by_account` - the maximum number of unfinalized blocks which one relay can store in the client's storage.
     /// * `trusted_signer` - the account address of the trusted signer which is allowed to submit light client updates.
+    /// * `sparse_mode` - whether the client stores only the execution blocks of the finalized checkpoints.
+    /// * `store_header_roots` - whether the client stores receipts root, state root and timestamp of the finalized execution blocks.
     pub fn init_contract(
         &self,
         ethereum_network: EthNetwork,
+        network_config: Option<NetworkConfig>,
         finalized_execution_header: BlockHeader,
         finalized_beacon_header: ExtendedBeaconBlockHeader,
         current_sync_committee: SyncCommittee,
@@ -50,10 +55,13 @@ impl EthClientContract {
         verify_bls_signatures: Option<bool>,
         hashes_gc_threshold: Option<u64>,
         trusted_signer: Option<AccountId>,
+        sparse_mode: Option<bool>,
+        store_header_roots: Option<bool>,
     ) {
         #[derive(BorshSerialize, Serialize)]
         pub struct InitInput {
             pub network: String,
+            pub network_config: Option<NetworkConfig>,
             pub finalized_execution_header: eth_types::BlockHeader,
             pub finalized_beacon_header: ExtendedBeaconBlockHeader,
             pub current_sync_committee: SyncCommittee,
@@ -62,10 +70,14 @@ impl EthClientContract {
             pub verify_bls_signatures: bool,
             pub hashes_gc_threshold: u64,
             pub trusted_signer: Option<AccountId>,
+            pub sparse_mode: bool,
+            pub store_header_roots: bool,
+            pub parameter_change_delay: Option<u64>,
         }
 
         let init_input = InitInput {
             network: ethereum_network.to_string(),
+            network_config,
             finalized_execution_header,
             finalized_beacon_header,
             current_sync_committee,
@@ -74,6 +86,9 @@ impl EthClientContract {
             verify_bls_signatures: verify_bls_signatures.unwrap_or(false),
             hashes_gc_threshold: hashes_gc_threshold.unwrap_or(51_000),
             trusted_signer,
+            sparse_mode: sparse_mode.unwrap_or(false),
+            store_header_roots: store_header_roots.unwrap_or(false),
+            parameter_change_delay: None,
         };
 
         println!(
@@ -114,6 +129,39 @@ impl EthClientContractTrait for EthClientContract {
         )
     }
 
+    fn send_light_client_updates(
+        &mut self,
+        light_client_updates: Vec<LightClientUpdate>,
+    ) -> Result<FinalExecutionOutcomeView, Box<dyn Error>> {
+        self.contract_wrapper.call_change_method(
+            "submit_beacon_chain_light_client_updates".to_string(),
+            light_client_updates.try_to_vec()?,
+            None,
+            None,
+        )
+    }
+
+    fn send_best_light_client_update(
+        &mut self,
+        light_client_update: LightClientUpdate,
+    ) -> Result<FinalExecutionOutcomeView, Box<dyn Error>> {
+        self.contract_wrapper.call_change_method(
+            "submit_beacon_chain_best_light_client_update".to_string(),
+            light_client_update.try_to_vec()?,
+            None,
+            None,
+        )
+    }
+
+    fn force_light_client_update(&mut self) -> Result<FinalExecutionOutcomeView, Box<dyn Error>> {
+        self.contract_wrapper.call_change_method(
+            "force_light_client_update".to_string(),
+            json!({}).to_string().into_bytes(),
+            None,
+            None,
+        )
+    }
+
     fn get_finalized_beacon_block_hash(&self) -> Result<H256, Box<dyn Error>> {
         let result = self.contract_wrapper.call_view_function(
             "finalized_beacon_block_root".to_string(),
@@ -187,6 +235,24 @@ impl EthClientContractTrait for EthClientContract {
         let beacon_block_number: Option<u64> = Option::<u64>::try_from_slice(&response)?;
         Ok(beacon_block_number)
     }
+
+    fn get_network_config(&self) -> Result<NetworkConfig, Box<dyn Error>> {
+        let response = self.contract_wrapper.call_view_function(
+            "get_network_config".to_string(),
+            json!({}).to_string().into_bytes(),
+        )?;
+
+        Ok(NetworkConfig::try_from_slice(&response)?)
+    }
+
+    fn is_sparse_mode(&self) -> Result<bool, Box<dyn Error>> {
+        let response = self.contract_wrapper.call_view_function(
+            "is_sparse_mode".to_string(),
+            json!({}).to_string().into_bytes(),
+        )?;
+
+        Ok(serde_json::from_slice(&response)?)
+    }
 }
 
 #[cfg(test)]
@@ -327,6 +393,7 @@ mod tests {
 
         eth_client_contract.init_contract(
             eth_client_contract::EthNetwork::Goerli,
+            None,
             finalized_execution_header.unwrap(),
             finalized_beacon_header,
             current_sync_committee,
@@ -335,6 +402,8 @@ mod tests {
             Some(false),
             None,
             Option::<AccountId>::Some(trusted_signer.parse().unwrap()),
+            None,
+            None,
         );
         eth_state.current_light_client_update = 1;
     }
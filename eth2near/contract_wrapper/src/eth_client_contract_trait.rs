@@ -1,3 +1,4 @@
+use eth2_utility::consensus::NetworkConfig;
 use eth2_utility::types::ClientMode;
 use eth_types::eth2::{LightClientState, LightClientUpdate};
 use eth_types::{BlockHeader, H256};
@@ -12,6 +13,24 @@ pub trait EthClientContractTrait {
         light_client_update: LightClientUpdate,
     ) -> Result<FinalExecutionOutcomeView, Box<dyn Error>>;
 
+    /// Submits the chain of Light Client Updates from the consecutive sync committee periods
+    /// to Ethereum Light Client on NEAR. Returns the final execution outcome or an error
+    fn send_light_client_updates(
+        &mut self,
+        light_client_updates: Vec<LightClientUpdate>,
+    ) -> Result<FinalExecutionOutcomeView, Box<dyn Error>>;
+
+    /// Submits the Light Client Update without the finality as the best valid update
+    /// to Ethereum Light Client on NEAR. Returns the final execution outcome or an error
+    fn send_best_light_client_update(
+        &mut self,
+        light_client_update: LightClientUpdate,
+    ) -> Result<FinalExecutionOutcomeView, Box<dyn Error>>;
+
+    /// Applies the best valid update stored in Ethereum Light Client on NEAR.
+    /// Returns the final execution outcome or an error
+    fn force_light_client_update(&mut self) -> Result<FinalExecutionOutcomeView, Box<dyn Error>>;
+
     /// Gets finalized beacon block hash from Ethereum Light Client on NEAR
     fn get_finalized_beacon_block_hash(&self) -> Result<H256, Box<dyn Error>>;
 
@@ -36,4 +55,10 @@ pub trait EthClientContractTrait {
     fn get_last_block_number(&self) -> Result<u64, Box<dyn Error>>;
 
     fn get_unfinalized_tail_block_number(&self) -> Result<Option<u64>, Box<dyn Error>>;
+
+    /// Gets the network config (genesis validators root and fork schedule) of the Ethereum Light Client on NEAR
+    fn get_network_config(&self) -> Result<NetworkConfig, Box<dyn Error>>;
+
+    /// Checks if the Ethereum Light Client on NEAR stores only the blocks of the finalized checkpoints
+    fn is_sparse_mode(&self) -> Result<bool, Box<dyn Error>>;
 }
@@ -11,6 +11,8 @@ pub enum EthNetwork {
     Ropsten,
     Goerli,
     Sepolia,
+    Holesky,
+    Hoodi,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -20,7 +22,7 @@ impl Display for IncorrectEthNetwork {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Unknown Ethereum network. Possible networks: 'Mainnet', 'Kiln', 'Goerli', 'Ropsten', 'Sepolia'"
+            "Unknown Ethereum network. Possible networks: 'Mainnet', 'Kiln', 'Goerli', 'Ropsten', 'Sepolia', 'Holesky', 'Hoodi'"
         )
     }
 }
@@ -41,6 +43,8 @@ impl EthNetwork {
             EthNetwork::Goerli => "goerli",
             EthNetwork::Ropsten => "ropsten",
             EthNetwork::Sepolia => "sepolia",
+            EthNetwork::Holesky => "holesky",
+            EthNetwork::Hoodi => "hoodi",
         }
     }
 }
@@ -55,6 +59,8 @@ impl FromStr for EthNetwork {
             "goerli" => Ok(EthNetwork::Goerli),
             "ropsten" => Ok(EthNetwork::Ropsten),
             "sepolia" => Ok(EthNetwork::Sepolia),
+            "holesky" => Ok(EthNetwork::Holesky),
+            "hoodi" => Ok(EthNetwork::Hoodi),
             _ => Err(IncorrectEthNetwork),
         }
     }
@@ -1,5 +1,6 @@
 use crate::eth_client_contract::EthClientContract;
 use crate::eth_client_contract_trait::EthClientContractTrait;
+use eth2_utility::consensus::NetworkConfig;
 use eth2_utility::types::ClientMode;
 use eth_types::eth2::{LightClientState, LightClientUpdate};
 use eth_types::{BlockHeader, H256};
@@ -57,6 +58,36 @@ impl EthClientContractTrait for FileEthClientContract {
             .send_light_client_update(light_client_update)
     }
 
+    fn send_light_client_updates(
+        &mut self,
+        light_client_updates: Vec<LightClientUpdate>,
+    ) -> Result<FinalExecutionOutcomeView, Box<dyn Error>> {
+        for light_client_update in &light_client_updates {
+            self.light_client_updates_file.write_all(
+                serde_json::to_string(light_client_update)
+                    .unwrap()
+                    .as_bytes(),
+            )?;
+            self.light_client_updates_file.write_all(",".as_bytes())?;
+        }
+        self.light_client_updates_file.flush()?;
+
+        self.eth_client_contract
+            .send_light_client_updates(light_client_updates)
+    }
+
+    fn send_best_light_client_update(
+        &mut self,
+        light_client_update: LightClientUpdate,
+    ) -> Result<FinalExecutionOutcomeView, Box<dyn Error>> {
+        self.eth_client_contract
+            .send_best_light_client_update(light_client_update)
+    }
+
+    fn force_light_client_update(&mut self) -> Result<FinalExecutionOutcomeView, Box<dyn Error>> {
+        self.eth_client_contract.force_light_client_update()
+    }
+
     fn get_finalized_beacon_block_hash(&self) -> Result<H256, Box<dyn Error>> {
         self.eth_client_contract.get_finalized_beacon_block_hash()
     }
@@ -94,4 +125,12 @@ impl EthClientContractTrait for FileEthClientContract {
     fn get_unfinalized_tail_block_number(&self) -> Result<Option<u64>, Box<dyn Error>> {
         self.eth_client_contract.get_unfinalized_tail_block_number()
     }
+
+    fn get_network_config(&self) -> Result<NetworkConfig, Box<dyn Error>> {
+        self.eth_client_contract.get_network_config()
+    }
+
+    fn is_sparse_mode(&self) -> Result<bool, Box<dyn Error>> {
+        self.eth_client_contract.is_sparse_mode()
+    }
 }
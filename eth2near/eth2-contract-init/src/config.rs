@@ -1,6 +1,7 @@
 use contract_wrapper::eth_network::EthNetwork;
 use contract_wrapper::near_network::NearNetwork;
 use contract_wrapper::near_rpc_client::NearRPCClient;
+use eth2_utility::consensus::NetworkConfig;
 use eth_rpc_client::beacon_rpc_client;
 use reqwest::Url;
 use serde::Deserialize;
@@ -30,6 +31,10 @@ pub struct Config {
     // The Ethereum network name (mainnet, kiln, ropsten, goerli)
     pub ethereum_network: EthNetwork,
 
+    // Network config (genesis validators root and fork schedule) for networks without
+    // a built-in config, e.g. private devnets
+    pub network_config: Option<NetworkConfig>,
+
     // NEAR network name (mainnet, testnet)
     pub near_network_id: NearNetwork,
 
@@ -49,6 +54,12 @@ pub struct Config {
 
     pub trusted_signer_account_id: Option<String>,
 
+    // Store only the execution blocks of the finalized checkpoints in the client
+    pub sparse_mode: Option<bool>,
+
+    // Store receipts root, state root and timestamp of the finalized execution blocks in the client
+    pub store_header_roots: Option<bool>,
+
     /// The trusted block root for checkpoint for contract initialization
     /// e.g.: 0x9cd0c5a8392d0659426b12384e8440c147510ab93eeaeccb08435a462d7bb1c7
     pub init_block_root: Option<String>,
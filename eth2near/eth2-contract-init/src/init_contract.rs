@@ -140,6 +140,7 @@ pub fn init_contract(
 
     eth_client_contract.init_contract(
         config.ethereum_network.clone(),
+        config.network_config.clone(),
         finalized_execution_header,
         finalized_header,
         light_client_snapshot.current_sync_committee,
@@ -148,6 +149,8 @@ pub fn init_contract(
         config.verify_bls_signature,
         config.hashes_gc_threshold,
         trusted_signature,
+        config.sparse_mode,
+        config.store_header_roots,
     );
 
     thread::sleep(time::Duration::from_secs(30));
@@ -193,6 +196,7 @@ mod tests {
             path_to_signer_secret_key: "NaN".to_string(),
             contract_account_id: "NaN".to_string(),
             ethereum_network: config_for_test.network_name.clone(),
+            network_config: None,
             near_network_id: NearNetwork::Testnet,
             output_dir: None,
             eth_requests_timeout_seconds: Some(30),
@@ -204,6 +208,8 @@ mod tests {
                 eth_client_contract.get_signer_account_id().to_string(),
             ),
             init_block_root: None,
+            sparse_mode: None,
+            store_header_roots: None,
             beacon_rpc_version: BeaconRPCVersion::V1_1,
         };
     }
@@ -274,7 +280,7 @@ mod tests {
             .expect("Error on getting last finalized beacon block slot(Eth client)");
 
         const MAX_GAP_IN_EPOCH_BETWEEN_FINALIZED_SLOTS: u64 = 3;
-        
+
         assert!(
             last_finalized_slot_eth_client
                 + ONE_EPOCH_IN_SLOTS * MAX_GAP_IN_EPOCH_BETWEEN_FINALIZED_SLOTS
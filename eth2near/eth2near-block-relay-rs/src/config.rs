@@ -90,6 +90,15 @@ pub struct Config {
     pub beacon_rpc_version: BeaconRPCVersion,
 
     pub get_light_client_update_by_epoch: Option<bool>,
+
+    // The max number of light client updates submitted in one transaction when the client
+    // is behind by several sync committee periods. The updates are sent one by one if not set.
+    // The value is capped by the contract's limit of 4 updates per transaction
+    pub max_light_client_updates_per_batch: Option<u64>,
+
+    // Submit the best non-finalized updates and force them when the finality is stuck
+    // for longer than the contract's update timeout
+    pub submit_best_light_client_updates: Option<bool>,
 }
 
 impl Config {
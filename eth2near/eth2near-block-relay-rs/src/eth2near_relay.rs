@@ -8,7 +8,9 @@ use crate::prometheus_metrics::{
 use bitvec::macros::internal::funty::Fundamental;
 use contract_wrapper::eth_client_contract_trait::EthClientContractTrait;
 use contract_wrapper::near_rpc_client::NearRPCClient;
-use eth2_utility::consensus::{EPOCHS_PER_SYNC_COMMITTEE_PERIOD, SLOTS_PER_EPOCH};
+use eth2_utility::consensus::{
+    NetworkConfig, EPOCHS_PER_SYNC_COMMITTEE_PERIOD, SLOTS_PER_EPOCH, UPDATE_TIMEOUT,
+};
 use eth2_utility::types::ClientMode;
 use eth_rpc_client::beacon_rpc_client::BeaconRPCClient;
 use eth_rpc_client::eth1_rpc_client::Eth1RPCClient;
@@ -28,6 +30,8 @@ use std::{cmp, fmt};
 use types::Slot;
 
 const ONE_EPOCH_IN_SLOTS: u64 = 32;
+// The max number of light client updates accepted by `submit_beacon_chain_light_client_updates`
+const MAX_LIGHT_CLIENT_UPDATES_PER_BATCH: u64 = 4;
 
 macro_rules! skip_fail {
     ($res:expr, $msg:expr, $sleep_time:expr) => {
@@ -98,7 +102,7 @@ pub struct Eth2NearRelay {
     near_rpc_client: NearRPCClient,
     eth_client_contract: Box<dyn EthClientContractTrait>,
     headers_batch_size: u64,
-    ethereum_network: String,
+    network_config: NetworkConfig,
     interval_between_light_client_updates_submission_in_epochs: u64,
     max_blocks_for_finalization: u64,
     near_network_name: String,
@@ -107,6 +111,8 @@ pub struct Eth2NearRelay {
     sleep_time_on_sync_secs: u64,
     sleep_time_after_submission_secs: u64,
     get_light_client_update_by_epoch: bool,
+    max_light_client_updates_per_batch: u64,
+    submit_best_light_client_updates: bool,
 }
 
 impl Eth2NearRelay {
@@ -123,13 +129,17 @@ impl Eth2NearRelay {
             Self::get_light_client_update_from_file(config, &beacon_rpc_client)
                 .expect("Error on parsing light client update");
 
+        let network_config = eth_contract
+            .get_network_config()
+            .expect("Error on fetching network config from Eth2Client");
+
         let eth2near_relay = Eth2NearRelay {
             beacon_rpc_client,
             eth1_rpc_client: Eth1RPCClient::new(&config.eth1_endpoint),
             eth_client_contract: eth_contract,
             near_rpc_client: NearRPCClient::new(&config.near_endpoint),
             headers_batch_size: config.headers_batch_size as u64,
-            ethereum_network: config.ethereum_network.to_string(),
+            network_config,
             interval_between_light_client_updates_submission_in_epochs: config
                 .interval_between_light_client_updates_submission_in_epochs,
             max_blocks_for_finalization: config.max_blocks_for_finalization,
@@ -141,6 +151,13 @@ impl Eth2NearRelay {
             get_light_client_update_by_epoch: config
                 .get_light_client_update_by_epoch
                 .unwrap_or(false),
+            max_light_client_updates_per_batch: config
+                .max_light_client_updates_per_batch
+                .unwrap_or(1)
+                .min(MAX_LIGHT_CLIENT_UPDATES_PER_BATCH),
+            submit_best_light_client_updates: config
+                .submit_best_light_client_updates
+                .unwrap_or(false),
         };
 
         if let Some(port) = config.prometheus_metrics_port {
@@ -249,11 +266,26 @@ impl Eth2NearRelay {
     fn submit_headers(&mut self) -> bool {
         info!(target: "relay", "Submit Headers mode");
 
-        let min_block_number = return_val_on_fail!(
-            self.eth_client_contract.get_last_block_number(),
-            "Failed to get last block number",
+        let sparse_mode = return_val_on_fail!(
+            self.eth_client_contract.is_sparse_mode(),
+            "Failed to get sparse mode",
             false
-        ) + 1;
+        );
+
+        // Only the finalized block is submitted in the sparse mode
+        let min_block_number = if sparse_mode {
+            return_val_on_fail!(
+                self.get_max_block_number(),
+                "Failed to fetch max block number",
+                false
+            )
+        } else {
+            return_val_on_fail!(
+                self.eth_client_contract.get_last_block_number(),
+                "Failed to get last block number",
+                false
+            ) + 1
+        };
 
         loop {
             info!(target: "relay", "= Creating headers batch =");
@@ -396,11 +428,41 @@ impl Eth2NearRelay {
         };
 
         finality_update_verify::is_correct_finality_update(
-            &self.ethereum_network,
+            &self.network_config,
             light_client_update,
             sync_committee,
         )
     }
+
+    fn verify_bls_signatures_for_finality_updates(
+        &mut self,
+        light_client_updates: &[LightClientUpdate],
+    ) -> Result<bool, Box<dyn Error>> {
+        let (first_update, next_updates) = light_client_updates
+            .split_first()
+            .ok_or("The list of light client updates is empty")?;
+        if !self.verify_bls_signature_for_finality_update(first_update)? {
+            return Ok(false);
+        }
+
+        // Each next update is signed by the sync committee rotated by the previous update
+        for (prev_update, light_client_update) in light_client_updates.iter().zip(next_updates) {
+            let sync_committee = match &prev_update.sync_committee_update {
+                Some(sync_committee_update) => sync_committee_update.next_sync_committee.clone(),
+                None => return Ok(false),
+            };
+
+            if !finality_update_verify::is_correct_finality_update(
+                &self.network_config,
+                light_client_update,
+                sync_committee,
+            )? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
 }
 
 // Implementation of functions for submitting light client updates
@@ -451,9 +513,85 @@ impl Eth2NearRelay {
             return true;
         }
 
+        if self.submit_best_light_client_updates && !self.is_shot_run_mode() {
+            return self.send_best_light_client_update(last_finalized_slot_on_near);
+        }
+
         return false;
     }
 
+    fn send_best_light_client_update(&mut self, last_finalized_slot_on_near: u64) -> bool {
+        let last_slot_on_eth = return_val_on_fail!(
+            self.beacon_rpc_client.get_last_slot_number(),
+            "Error on getting last slot on Ethereum. Skipping sending best light client update",
+            false
+        )
+        .as_u64();
+
+        let attested_slot = last_finalized_slot_on_near + UPDATE_TIMEOUT;
+        if last_slot_on_eth <= attested_slot {
+            return false;
+        }
+
+        info!(target: "relay", "= Sending best light client update. No finality for {} slots =", last_slot_on_eth - last_finalized_slot_on_near);
+
+        let include_next_sync_committee =
+            BeaconRPCClient::get_period_for_slot(last_finalized_slot_on_near)
+                != BeaconRPCClient::get_period_for_slot(attested_slot);
+        let light_client_update = return_val_on_fail!(
+            HandMadeFinalityLightClientUpdate::get_best_light_client_update(
+                &self.beacon_rpc_client,
+                attested_slot,
+                include_next_sync_committee,
+            ),
+            format!(
+                "Error on getting best light client update for attested slot={}.",
+                attested_slot
+            ),
+            false
+        );
+
+        let verification_result = return_val_on_fail!(
+            self.verify_bls_signature_for_finality_update(&light_client_update),
+            "Error on bls verification. Skip sending the best light client update",
+            false
+        );
+        if !verification_result {
+            warn!(target: "relay", "NOT PASS bls signature verification. Skip sending the best light client update");
+            return false;
+        }
+
+        let execution_outcome = return_val_on_fail_and_sleep!(
+            self.eth_client_contract
+                .send_best_light_client_update(light_client_update),
+            "Fail to send best light client update",
+            self.sleep_time_on_sync_secs,
+            false
+        );
+        if let FinalExecutionStatus::Failure(error_message) = execution_outcome.status {
+            FAILS_ON_UPDATES_SUBMISSION.inc();
+            warn!(target: "relay", "FAIL status on best Light Client Update submission. Error: {:?}", error_message);
+            return false;
+        }
+
+        let execution_outcome = return_val_on_fail_and_sleep!(
+            self.eth_client_contract.force_light_client_update(),
+            "Fail to force light client update",
+            self.sleep_time_on_sync_secs,
+            false
+        );
+        if let FinalExecutionStatus::Failure(error_message) = execution_outcome.status {
+            FAILS_ON_UPDATES_SUBMISSION.inc();
+            warn!(target: "relay", "FAIL status on forcing Light Client Update. Error: {:?}", error_message);
+            return false;
+        }
+
+        info!(target: "relay", "Successful forced light client update! Transaction URL: https://explorer.{}.near.org/transactions/{}",
+                                  self.near_network_name, execution_outcome.transaction.hash);
+        sleep(Duration::from_secs(self.sleep_time_after_submission_secs));
+        return true;
+    }
+
     fn send_light_client_updates(
         &mut self,
         last_finalized_slot_on_near: u64,
@@ -508,6 +646,27 @@ impl Eth2NearRelay {
         let end_period = BeaconRPCClient::get_period_for_slot(last_finalized_slot_on_eth);
         info!(target: "relay", "Last finalized slot/period on ethereum={}/{}", last_finalized_slot_on_eth, end_period);
 
+        if end_period > last_eth2_period_on_near_chain + 1
+            && self.max_light_client_updates_per_batch > 1
+        {
+            debug!(target: "relay", "Finalized period on NEAR is behind by several periods. Fetching a batch of sync commity updates");
+            let last_batch_period = cmp::min(
+                end_period,
+                last_eth2_period_on_near_chain + self.max_light_client_updates_per_batch,
+            );
+
+            let mut light_client_updates = vec![];
+            for period in last_eth2_period_on_near_chain + 1..=last_batch_period {
+                light_client_updates.push(return_on_fail!(
+                    self.beacon_rpc_client.get_light_client_update(period),
+                    "Error on getting light client update. Skipping sending light client updates"
+                ));
+            }
+
+            self.send_specific_light_client_updates(light_client_updates);
+            return;
+        }
+
         let light_client_update = if end_period == last_eth2_period_on_near_chain {
             debug!(target: "relay", "Finalized period on ETH and NEAR are equal. Don't fetch sync commity update");
             return_on_fail!(
@@ -693,6 +852,47 @@ impl Eth2NearRelay {
         sleep(Duration::from_secs(self.sleep_time_after_submission_secs));
         return true;
     }
+
+    fn send_specific_light_client_updates(
+        &mut self,
+        light_client_updates: Vec<LightClientUpdate>,
+    ) -> bool {
+        let verification_result = return_val_on_fail!(
+            self.verify_bls_signatures_for_finality_updates(&light_client_updates),
+            "Error on bls verification. Skip sending the light client updates",
+            false
+        );
+
+        if verification_result {
+            info!(target: "relay", "PASS bls signature verification!");
+        } else {
+            warn!(target: "relay", "NOT PASS bls signature verification. Skip sending these light client updates");
+            return false;
+        }
+
+        let updates_count = light_client_updates.len();
+        let execution_outcome = return_val_on_fail_and_sleep!(
+            self.eth_client_contract
+                .send_light_client_updates(light_client_updates),
+            "Fail to send light client updates",
+            self.sleep_time_on_sync_secs,
+            false
+        );
+
+        info!(target: "relay", "Sending {} light client updates", updates_count);
+
+        if let FinalExecutionStatus::Failure(error_message) = execution_outcome.status {
+            FAILS_ON_UPDATES_SUBMISSION.inc();
+            warn!(target: "relay", "FAIL status on Light Client Updates submission. Error: {:?}", error_message);
+            return false;
+        }
+
+        info!(target: "relay", "Successful light client updates submission! Transaction URL: https://explorer.{}.near.org/transactions/{}",
+                                  self.near_network_name, execution_outcome.transaction.hash);
+
+        sleep(Duration::from_secs(self.sleep_time_after_submission_secs));
+        true
+    }
 }
 
 #[cfg(test)]
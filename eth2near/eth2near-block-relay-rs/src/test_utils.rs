@@ -76,6 +76,7 @@ pub fn init_contract_from_files(
 
     eth_client_contract.init_contract(
         config_for_test.network_name.clone(),
+        None,
         finalized_execution_header.unwrap(),
         finalized_beacon_header,
         current_sync_committee,
@@ -84,6 +85,8 @@ pub fn init_contract_from_files(
         Some(false),
         None,
         Some(eth_client_contract.contract_wrapper.get_signer_account_id()),
+        None,
+        None,
     );
     thread::sleep(time::Duration::from_secs(30));
 }
@@ -145,6 +148,7 @@ pub fn init_contract_from_specific_slot(
 
     eth_client_contract.init_contract(
         config_for_test.network_name.clone(),
+        None,
         finalized_execution_header,
         finalized_beacon_header,
         current_sync_committee,
@@ -153,6 +157,8 @@ pub fn init_contract_from_specific_slot(
         Some(false),
         None,
         Some(eth_client_contract.contract_wrapper.get_signer_account_id()),
+        None,
+        None,
     );
 
     thread::sleep(time::Duration::from_secs(30));
@@ -199,6 +205,8 @@ fn get_config(config_for_test: &ConfigForTests) -> Config {
         max_submitted_blocks_by_account: None,
         beacon_rpc_version: BeaconRPCVersion::V1_5,
         get_light_client_update_by_epoch: Some(false),
+        max_light_client_updates_per_batch: None,
+        submit_best_light_client_updates: None,
     }
 }
 
@@ -214,6 +222,7 @@ fn get_init_config(
         path_to_signer_secret_key: "NaN".to_string(),
         contract_account_id: "NaN".to_string(),
         ethereum_network: config_for_test.network_name.clone(),
+        network_config: None,
         near_network_id: NearNetwork::Testnet,
         output_dir: None,
         eth_requests_timeout_seconds: Some(30),
@@ -223,6 +232,8 @@ fn get_init_config(
         max_submitted_blocks_by_account: Some(8000),
         trusted_signer_account_id: Some(eth_client_contract.get_signer_account_id().to_string()),
         init_block_root: None,
+        sparse_mode: None,
+        store_header_roots: None,
         beacon_rpc_version: BeaconRPCVersion::V1_5,
     }
 }
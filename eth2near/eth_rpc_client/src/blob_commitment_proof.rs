@@ -0,0 +1,134 @@
+use crate::beacon_block_body_merkle_tree::BeaconBlockBodyMerkleTree;
+use crate::errors::{MerkleTreeError, MissBlobKzgCommitment};
+use eth_types::eth2::KzgCommitment;
+use ethereum_types::H256;
+use merkle_proof::MerkleTree;
+use std::error::Error;
+use tree_hash::TreeHash;
+use types::{BeaconBlockBody, MainnetEthSpec};
+
+/// `BlobCommitmentProof` contains a `blob_kzg_commitment` and a proof of its inclusion in the
+/// `BeaconBlockBody` tree hash.
+/// `blob_kzg_commitments` is the 12th field in `BeaconBlockBody`.
+/// The first 12 elements in proof correspondent to the proof of inclusion of the commitment
+/// in the Merkle tree built for the `blob_kzg_commitments` list, the 13th element is the length
+/// of the list mixed in with its root.
+/// The last 4 elements of the proof of `blob_kzg_commitments` in the Merkle tree are built
+/// on high-level `BeaconBlockBody` fields.
+/// The proof starts from the leaf.
+pub struct BlobCommitmentProof {
+    blob_index: usize,
+    blob_kzg_commitment: KzgCommitment,
+    proof: Vec<H256>,
+}
+
+impl BlobCommitmentProof {
+    pub const L1_BEACON_BLOCK_BODY_TREE_BLOB_KZG_COMMITMENTS_INDEX: usize = 11;
+    pub const L1_BEACON_BLOCK_BODY_PROOF_SIZE: usize = 4;
+    /// log2(MAX_BLOB_COMMITMENTS_PER_BLOCK)
+    pub const L2_BLOB_KZG_COMMITMENTS_TREE_DEPTH: usize = 12;
+    pub const PROOF_SIZE: usize =
+        Self::L2_BLOB_KZG_COMMITMENTS_TREE_DEPTH + 1 + Self::L1_BEACON_BLOCK_BODY_PROOF_SIZE;
+
+    pub fn construct_from_beacon_block_body(
+        beacon_block_body: &BeaconBlockBody<MainnetEthSpec>,
+        blob_index: usize,
+    ) -> Result<Self, Box<dyn Error>> {
+        let blob_kzg_commitments = beacon_block_body
+            .blob_kzg_commitments()
+            .map_err(|_| MissBlobKzgCommitment)?;
+        let blob_kzg_commitment = blob_kzg_commitments
+            .get(blob_index)
+            .ok_or(MissBlobKzgCommitment)?;
+
+        let leaves: Vec<H256> = blob_kzg_commitments
+            .iter()
+            .map(|commitment| commitment.tree_hash_root())
+            .collect();
+        let blob_kzg_commitments_merkle_tree =
+            MerkleTree::create(&leaves, Self::L2_BLOB_KZG_COMMITMENTS_TREE_DEPTH);
+        let mut proof = blob_kzg_commitments_merkle_tree
+            .generate_proof(blob_index, Self::L2_BLOB_KZG_COMMITMENTS_TREE_DEPTH)
+            .map_err(|err| MerkleTreeError(err))?
+            .1;
+        proof.push(blob_kzg_commitments.len().tree_hash_root());
+
+        let beacon_block_merkle_tree = &BeaconBlockBodyMerkleTree::new(beacon_block_body);
+        let l1_blob_kzg_commitments_proof = beacon_block_merkle_tree
+            .0
+            .generate_proof(
+                Self::L1_BEACON_BLOCK_BODY_TREE_BLOB_KZG_COMMITMENTS_INDEX,
+                Self::L1_BEACON_BLOCK_BODY_PROOF_SIZE,
+            )
+            .map_err(|err| MerkleTreeError(err))?
+            .1;
+        proof.extend(&l1_blob_kzg_commitments_proof);
+
+        Ok(Self {
+            blob_index,
+            blob_kzg_commitment: KzgCommitment(blob_kzg_commitment.0),
+            proof,
+        })
+    }
+
+    pub fn get_proof(&self) -> Vec<H256> {
+        self.proof.clone()
+    }
+
+    pub fn get_blob_index(&self) -> usize {
+        self.blob_index
+    }
+
+    pub fn get_blob_kzg_commitment(&self) -> KzgCommitment {
+        self.blob_kzg_commitment.clone()
+    }
+
+    pub fn verify_proof_for_hash(&self, beacon_block_body_hash: &H256) -> bool {
+        let index = (Self::L1_BEACON_BLOCK_BODY_TREE_BLOB_KZG_COMMITMENTS_INDEX
+            << (Self::L2_BLOB_KZG_COMMITMENTS_TREE_DEPTH + 1))
+            | self.blob_index;
+
+        merkle_proof::verify_merkle_proof(
+            self.blob_kzg_commitment.tree_hash_root(),
+            &self.proof,
+            Self::PROOF_SIZE,
+            index,
+            *beacon_block_body_hash,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlobCommitmentProof;
+    use tree_hash::TreeHash;
+    use types::{
+        BeaconBlockBody, BeaconBlockDeneb, ChainSpec, EmptyBlock, KzgCommitment, MainnetEthSpec,
+    };
+
+    #[test]
+    fn test_blob_commitment_proof_verification() {
+        let spec = ChainSpec::mainnet();
+        let mut body = BeaconBlockDeneb::<MainnetEthSpec>::empty(&spec).body;
+        for i in 0..3 {
+            body.blob_kzg_commitments
+                .push(KzgCommitment([i; 48]))
+                .unwrap();
+        }
+        let beacon_block_body = BeaconBlockBody::Deneb(body);
+        let body_root = beacon_block_body.tree_hash_root();
+
+        let blob_commitment_proof =
+            BlobCommitmentProof::construct_from_beacon_block_body(&beacon_block_body, 2).unwrap();
+        assert_eq!(blob_commitment_proof.get_blob_kzg_commitment().0, [2; 48]);
+        assert_eq!(
+            blob_commitment_proof.get_proof().len(),
+            BlobCommitmentProof::PROOF_SIZE
+        );
+        assert!(blob_commitment_proof.verify_proof_for_hash(&body_root));
+
+        assert!(
+            BlobCommitmentProof::construct_from_beacon_block_body(&beacon_block_body, 3).is_err()
+        );
+    }
+}
@@ -113,6 +113,17 @@ impl Display for MissExecutionPayload {
 
 impl Error for MissExecutionPayload {}
 
+#[derive(Debug)]
+pub struct MissBlobKzgCommitment;
+
+impl Display for MissBlobKzgCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Miss blob KZG commitment")
+    }
+}
+
+impl Error for MissBlobKzgCommitment {}
+
 #[derive(Debug)]
 pub struct MerkleTreeError(pub merkle_proof::MerkleTreeError);
 
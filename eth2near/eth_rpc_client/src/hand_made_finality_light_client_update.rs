@@ -43,6 +43,71 @@ impl HandMadeFinalityLightClientUpdate {
         )
     }
 
+    /// Builds the update without the finality for the contract's best valid update.
+    /// The attested header is used as the active header of such update
+    pub fn get_best_light_client_update(
+        beacon_rpc_client: &BeaconRPCClient,
+        attested_slot: u64,
+        include_next_sync_committee: bool,
+    ) -> Result<LightClientUpdate, Box<dyn Error>> {
+        let (attested_slot, signature_slot) =
+            Self::get_attested_slot_with_enough_sync_committee_bits_sum(
+                beacon_rpc_client,
+                attested_slot,
+            )?;
+        trace!(target: "relay", "Best update attested slot = {} and signature slot = {}", attested_slot, signature_slot);
+
+        let signature_beacon_body =
+            beacon_rpc_client.get_beacon_block_body_for_block_id(&format!("{}", signature_slot))?;
+        let sync_aggregate = signature_beacon_body
+            .sync_aggregate()
+            .map_err(|_| MissSyncAggregationError)?;
+        let sync_committee_bits: [u8; 64] = Self::get_sync_committee_bits(sync_aggregate)?;
+
+        let attested_header = beacon_rpc_client
+            .get_beacon_block_header_for_block_id(&format!("{}", attested_slot))?;
+        let attested_block_body =
+            beacon_rpc_client.get_beacon_block_body_for_block_id(&format!("{}", attested_slot))?;
+        let attested_block_eth1data_proof =
+            ExecutionBlockProof::construct_from_beacon_block_body(&attested_block_body)?;
+
+        let sync_committee_update = if include_next_sync_committee {
+            let beacon_state = beacon_rpc_client.get_beacon_state(&format!("{}", attested_slot))?;
+            Some(Self::get_next_sync_committee(&beacon_state)?)
+        } else {
+            None
+        };
+
+        Ok(LightClientUpdate {
+            attested_beacon_header: Self::from_lighthouse_beacon_header(&attested_header),
+            sync_aggregate: eth_types::eth2::SyncAggregate {
+                sync_committee_bits: SyncCommitteeBits(sync_committee_bits),
+                sync_committee_signature: serde_json::from_str::<SignatureBytes>(
+                    &serde_json::to_string(&sync_aggregate.sync_committee_signature)?,
+                )?,
+            },
+            signature_slot,
+            finality_update: FinalizedHeaderUpdate {
+                header_update: HeaderUpdate {
+                    beacon_header: Self::from_lighthouse_beacon_header(&attested_header),
+                    execution_block_hash: eth_types::H256::from(
+                        attested_block_eth1data_proof
+                            .get_execution_block_hash()
+                            .0
+                            .to_vec(),
+                    ),
+                    execution_hash_branch: attested_block_eth1data_proof
+                        .get_proof()
+                        .iter()
+                        .map(|x| eth_types::H256::from(x.0.to_vec()))
+                        .collect(),
+                },
+                finality_branch: vec![],
+            },
+            sync_committee_update,
+        })
+    }
+
     pub fn get_finality_light_client_update_from_file(
         beacon_rpc_client: &BeaconRPCClient,
         file_name: &str,
@@ -1,5 +1,6 @@
 pub mod beacon_block_body_merkle_tree;
 pub mod beacon_rpc_client;
+pub mod blob_commitment_proof;
 pub mod config_for_tests;
 pub mod errors;
 pub mod eth1_rpc_client;
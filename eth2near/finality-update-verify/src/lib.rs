@@ -1,13 +1,12 @@
 use bitvec::order::Lsb0;
 use bitvec::prelude::BitVec;
 use eth2_utility::consensus::{
-    compute_domain, compute_signing_root, get_participant_pubkeys, Network, NetworkConfig,
+    compute_domain, compute_signing_root, get_pubkeys_for_aggregation, NetworkConfig,
     DOMAIN_SYNC_COMMITTEE, MIN_SYNC_COMMITTEE_PARTICIPANTS,
 };
 use eth_types::eth2::{BeaconBlockHeader, LightClientUpdate, SyncCommittee};
 use eth_types::H256;
 use std::error::Error;
-use std::str::FromStr;
 use types::{Hash256, Slot};
 
 #[cfg(test)]
@@ -34,13 +33,10 @@ fn to_lighthouse_beacon_block_header(
 }
 
 pub fn is_correct_finality_update(
-    ethereum_network: &str,
+    config: &NetworkConfig,
     light_client_update: &LightClientUpdate,
     sync_committee: SyncCommittee,
 ) -> Result<bool, Box<dyn Error>> {
-    let ethereum_network = Network::from_str(ethereum_network)?;
-    let config = NetworkConfig::new(&ethereum_network);
-
     let sync_committee_bits =
         BitVec::<u8, Lsb0>::from_slice(&light_client_update.sync_aggregate.sync_committee_bits.0);
 
@@ -52,15 +48,14 @@ pub fn is_correct_finality_update(
         return Ok(false);
     }
 
-    let participant_pubkeys =
-        get_participant_pubkeys(&sync_committee.pubkeys.0, &sync_committee_bits);
+    let aggregation_pubkeys = get_pubkeys_for_aggregation(&sync_committee, &sync_committee_bits);
     let fork_version = config
         .compute_fork_version_by_slot(light_client_update.signature_slot)
         .expect("Unsupported fork");
     let domain = compute_domain(
         DOMAIN_SYNC_COMMITTEE,
         fork_version,
-        config.genesis_validators_root.into(),
+        config.genesis_validators_root,
     );
 
     let attested_beacon_header_root = tree_hash::TreeHash::tree_hash_root(
@@ -79,7 +74,7 @@ pub fn is_correct_finality_update(
     )
     .map_err(|_err| -> String { "Error on aggregate signature deserialization".to_string() })?;
     let mut pubkeys: Vec<bls::PublicKey> = vec![];
-    for pubkey in participant_pubkeys {
+    for pubkey in aggregation_pubkeys {
         pubkeys.push(
             bls::PublicKey::deserialize(&pubkey.0)
                 .map_err(|_err| -> String { "Error on public key deserialization".to_string() })?,
@@ -96,8 +91,10 @@ pub fn is_correct_finality_update(
 mod tests {
     use crate::config_for_tests::ConfigForTests;
     use crate::is_correct_finality_update;
+    use eth2_utility::consensus::{Network, NetworkConfig};
     use eth_types::eth2::LightClientUpdate;
     use eth_types::eth2::SyncCommittee;
+    use std::str::FromStr;
 
     fn get_config() -> ConfigForTests {
         ConfigForTests::load_from_toml("config_for_tests.toml".try_into().unwrap())
@@ -106,6 +103,7 @@ mod tests {
     #[test]
     fn smoke_verify_finality_update() {
         let config = get_config();
+        let network_config = NetworkConfig::new(&Network::from_str(&config.network_name).unwrap());
 
         let light_client_updates: Vec<LightClientUpdate> = serde_json::from_str(
             &std::fs::read_to_string(config.path_to_light_client_updates)
@@ -125,14 +123,14 @@ mod tests {
         .unwrap();
 
         assert!(is_correct_finality_update(
-            &config.network_name,
+            &network_config,
             &light_client_updates[0],
             current_sync_committee
         )
         .unwrap());
 
         assert!(!is_correct_finality_update(
-            &config.network_name,
+            &network_config,
             &light_client_updates[0],
             next_sync_committee
         )
@@ -1,6 +1,6 @@
 use crate::{
     eth_rpc_client::EthRPCClient,
-    types::{BlockHeader, Log, TransactionReceipt},
+    types::{BlockHeader, Log, TransactionReceipt, Withdrawal},
     primitives::U8
 };
 use cita_trie::{MemoryDB, PatriciaTrie, Trie, TrieError};
@@ -20,6 +20,14 @@ pub struct Proof {
     pub proof: Vec<Vec<u8>>,
 }
 
+#[derive(Debug)]
+pub struct WithdrawalProof {
+    pub withdrawal_position: U64,
+    pub withdrawal_data: Vec<u8>,
+    pub header_data: Vec<u8>,
+    pub proof: Vec<Vec<u8>>,
+}
+
 pub fn get_proof_for_event(tx_hash: H256, log_index: u64, node_url: &str) -> Result<Proof, Box<dyn Error>> {
     let client = EthRPCClient::new(node_url);
 
@@ -52,6 +60,30 @@ pub fn get_proof_for_event(tx_hash: H256, log_index: u64, node_url: &str) -> Res
     })
 }
 
+pub fn get_proof_for_withdrawal(block_number: u64, withdrawal_index: u64, node_url: &str) -> Result<WithdrawalProof, Box<dyn Error>> {
+    let client = EthRPCClient::new(node_url);
+
+    let block_header = client.get_block_by_number(block_number.into())?;
+    let withdrawals = block_header.withdrawals.as_ref().ok_or("Block has no withdrawals")?;
+    let withdrawal_position = withdrawals
+        .iter()
+        .position(|withdrawal| withdrawal.index == withdrawal_index.into())
+        .ok_or("Withdrawal not found")?;
+
+    let mut trie = build_withdrawal_trie(withdrawals)?;
+    trie.root()?;
+
+    let withdrawal_key = rlp::encode(&U64::from(withdrawal_position));
+    let proof = trie.get_proof(&withdrawal_key)?;
+
+    Ok(WithdrawalProof {
+        withdrawal_position: withdrawal_position.into(),
+        withdrawal_data: encode_withdrawal(&withdrawals[withdrawal_position]),
+        header_data: encode_header(&block_header),
+        proof,
+    })
+}
+
 fn build_receipt_trie(receipts: &[TransactionReceipt],) -> Result<PatriciaTrie<MemoryDB, HasherKeccak>, TrieError> {
     let memdb = Arc::new(MemoryDB::new(true));
     let hasher = Arc::new(HasherKeccak::new());
@@ -67,6 +99,31 @@ fn build_receipt_trie(receipts: &[TransactionReceipt],) -> Result<PatriciaTrie<M
     Ok(trie)
 }
 
+fn build_withdrawal_trie(withdrawals: &[Withdrawal]) -> Result<PatriciaTrie<MemoryDB, HasherKeccak>, TrieError> {
+    let memdb = Arc::new(MemoryDB::new(true));
+    let hasher = Arc::new(HasherKeccak::new());
+    let mut trie = PatriciaTrie::new(memdb, hasher);
+
+    for (position, withdrawal) in withdrawals.iter().enumerate() {
+        let withdrawal_key = rlp::encode(&U64::from(position)).to_vec();
+        trie.insert(withdrawal_key, encode_withdrawal(withdrawal))?;
+    }
+
+    Ok(trie)
+}
+
+fn encode_withdrawal(withdrawal: &Withdrawal) -> Vec<u8> {
+    let mut stream = RlpStream::new();
+    stream.begin_list(4);
+    stream
+        .append(&withdrawal.index)
+        .append(&withdrawal.validator_index)
+        .append(&withdrawal.address)
+        .append(&withdrawal.amount);
+
+    stream.out().to_vec()
+}
+
 fn encode_receipt(receipt: &TransactionReceipt) -> Vec<u8> {
     let mut stream = RlpStream::new();
 
@@ -190,6 +247,53 @@ pub mod tests {
         verify_proof(proof, "post_dencun_proof.json");
     }
 
+    #[test]
+    fn withdrawal_proof_matches_trie_root() {
+        // Withdrawals and header of the Goerli block 8709522
+        let data_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+        let block: Value = serde_json::from_str(
+            &fs::read_to_string(
+                data_dir.join("eth_rpc_client/data/beacon_block_goerli_slot_5262172.json"),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let headers: Value = serde_json::from_str(
+            &fs::read_to_string(
+                data_dir.join("contract_wrapper/data/execution_block_headers_goerli_5262172-5262492.json"),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let parse_u64 = |value: &Value| U64::from_dec_str(value.as_str().unwrap()).unwrap();
+        let withdrawals = block["data"]["message"]["body"]["execution_payload"]["withdrawals"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|withdrawal| Withdrawal {
+                index: parse_u64(&withdrawal["index"]),
+                validator_index: parse_u64(&withdrawal["validator_index"]),
+                address: ethereum_types::Address::from_str(withdrawal["address"].as_str().unwrap())
+                    .unwrap(),
+                amount: parse_u64(&withdrawal["amount"]),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(withdrawals.len(), 16);
+
+        let mut trie = build_withdrawal_trie(&withdrawals).unwrap();
+        let root = trie.root().unwrap();
+        let header = &headers[0];
+        assert_eq!(header["number"], "0x84e592");
+        let expected_root = H256::from_str(header["withdrawals_root"].as_str().unwrap()).unwrap();
+        assert_eq!(root, expected_root.as_bytes());
+
+        let withdrawal_key = rlp::encode(&U64::from(5u64)).to_vec();
+        let proof = trie.get_proof(&withdrawal_key).unwrap();
+        let withdrawal_data = trie.verify_proof(&root, &withdrawal_key, proof).unwrap();
+        assert_eq!(withdrawal_data, Some(encode_withdrawal(&withdrawals[5])));
+    }
+
     fn read_proof_data(file_name: &str) -> (u64, u64, String, String, String, Vec<String>) {
         let mut data_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         data_dir.push("src/test_data");
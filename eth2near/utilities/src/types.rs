@@ -25,6 +25,16 @@ pub struct BlockHeader {
     pub blob_gas_used: Option<U64>,
     pub excess_blob_gas: Option<U64>,
     pub parent_beacon_block_root: Option<H256>,
+    pub withdrawals: Option<Vec<Withdrawal>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    pub index: U64,
+    pub validator_index: U64,
+    pub address: Address,
+    pub amount: U64,
 }
 
 #[derive(Debug, Clone, Deserialize)]